@@ -151,6 +151,7 @@ fn testnet_genesis(	wasm_binary: &[u8],
 		}),
 		rbac: Some(RbacConfig {
 			super_admins: vec![get_account_id_from_seed::<sr25519::Public>("Alice")]
-		})
+		}),
+		registrar: Some(Default::default()),
 	}
 }
@@ -0,0 +1,35 @@
+//! A collection of node-specific RPC methods.
+//!
+//! The node exposes the pallet RPCs of the runtime in addition to the core
+//! RPCs provided by Substrate.
+
+use std::sync::Arc;
+
+use cord_runtime::{opaque::Block, AccountId, BlockNumber, Hash, Moment};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+
+/// Full client dependencies.
+pub struct FullDeps<C> {
+	/// The client instance to use.
+	pub client: Arc<C>,
+}
+
+/// Instantiate all full RPC extensions.
+pub fn create_full<C>(deps: FullDeps<C>) -> jsonrpc_core::IoHandler<sc_rpc::Metadata>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: pallet_credential_rpc::CredentialRuntimeApi<Block, AccountId, Hash, Moment>,
+	C::Api: pallet_registrar_rpc::RegistrarRuntimeApi<Block, AccountId, Hash, BlockNumber>,
+{
+	use pallet_credential_rpc::{Credential, CredentialApi};
+	use pallet_registrar_rpc::{Registrar, RegistrarApi};
+
+	let mut io = jsonrpc_core::IoHandler::default();
+	let FullDeps { client } = deps;
+
+	io.extend_with(CredentialApi::to_delegate(Credential::new(client.clone())));
+	io.extend_with(RegistrarApi::to_delegate(Registrar::new(client)));
+
+	io
+}
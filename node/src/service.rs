@@ -29,7 +29,7 @@ pub fn new_full_params(config: Configuration) -> Result<(
 		Block, FullClient,
 		sc_consensus_aura::AuraImportQueue<Block, FullClient>,
 		sc_transaction_pool::FullPool<Block, FullClient>,
-		(), FullBackend,
+		jsonrpc_core::IoHandler<sc_rpc::Metadata>, FullBackend,
 	>,
 	FullSelectChain,
 	sp_inherents::InherentDataProviders,
@@ -74,6 +74,16 @@ pub fn new_full_params(config: Configuration) -> Result<(
 		config.prometheus_registry(),
 	)?;
 
+	let rpc_extensions_builder = {
+		let client = client.clone();
+
+		Box::new(move |_| {
+			let deps = crate::rpc::FullDeps { client: client.clone() };
+
+			crate::rpc::create_full(deps)
+		})
+	};
+
 	let provider = client.clone() as Arc<dyn StorageAndProofProvider<_, _>>;
 	let finality_proof_provider =
 		Arc::new(GrandpaFinalityProofProvider::new(backend.clone(), provider));
@@ -86,7 +96,7 @@ pub fn new_full_params(config: Configuration) -> Result<(
 		finality_proof_provider: Some(finality_proof_provider),
 		on_demand: None,
 		remote_blockchain: None,
-		rpc_extensions_builder: Box::new(|_| ()),
+		rpc_extensions_builder,
 	};
 
 	Ok((
@@ -0,0 +1,23 @@
+//! Runtime API definition for the Credential Registry pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+pub use pallet_credential::{
+    CredentialLookup, CredentialStatus, CredentialVerification, StatusListId, StatusListSnapshot, StatusPurpose,
+};
+
+sp_api::decl_runtime_apis! {
+    pub trait CredentialApi<AccountId, Hash, Moment> where
+        AccountId: Codec,
+        Hash: Codec,
+        Moment: Codec,
+    {
+        /// Verifies a credential referenced by id, or every credential anchored under a hash.
+        fn verify_credential(lookup: CredentialLookup<Hash>) -> Vec<CredentialVerification<AccountId, Hash, Moment>>;
+        /// Returns a status list with its packed bitstring, if it exists.
+        fn status_list(id: StatusListId) -> Option<StatusListSnapshot<AccountId, Hash>>;
+    }
+}
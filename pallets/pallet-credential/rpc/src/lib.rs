@@ -0,0 +1,134 @@
+//! RPC interface for the Credential Registry pallet.
+
+use codec::Codec;
+use flate2::{write::GzEncoder, Compression};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{io::Write, sync::Arc};
+
+pub use pallet_credential_rpc_runtime_api::{
+    CredentialApi as CredentialRuntimeApi, CredentialLookup, CredentialVerification, StatusListId,
+    StatusListSnapshot, StatusPurpose,
+};
+
+/// A status list as StatusList2021 expects it: the bitstring GZIP compressed and
+/// base64 encoded in `encodedList`, with the hash of the uncompressed bitstring.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncodedStatusList<AccountId, Hash> {
+    pub issuer: AccountId,
+    pub status_purpose: StatusPurpose,
+    pub length: u32,
+    pub encoded_list: String,
+    pub hash: Hash,
+}
+
+#[rpc]
+pub trait CredentialApi<BlockHash, AccountId, Hash, Moment> {
+    /// Verifies a credential given its id (`{"id": [..]}`) or the hash it was
+    /// anchored under (`{"hash": "0x.."}`). Returns an empty list when nothing
+    /// is anchored.
+    #[rpc(name = "credential_verify")]
+    fn verify(
+        &self,
+        lookup: CredentialLookup<Hash>,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<CredentialVerification<AccountId, Hash, Moment>>>;
+
+    /// Returns a status list in the StatusList2021 encoding, or `null` if it doesn't exist.
+    #[rpc(name = "credential_statusList")]
+    fn status_list(
+        &self,
+        id: StatusListId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<EncodedStatusList<AccountId, Hash>>>;
+}
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i64 = 1;
+/// Error code returned when a status list can't be encoded.
+const ENCODING_ERROR: i64 = 2;
+
+/// Implements the `CredentialApi` RPC trait by calling into the runtime.
+pub struct Credential<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Credential<C, B> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+// GZIP compresses and base64 encodes a bitstring, as in the StatusList2021 `encodedList`.
+fn encode_list(bits: &[u8]) -> std::io::Result<String> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bits)?;
+    Ok(base64::encode(encoder.finish()?))
+}
+
+impl<C, Block, AccountId, Hash, Moment>
+    CredentialApi<<Block as BlockT>::Hash, AccountId, Hash, Moment> for Credential<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: CredentialRuntimeApi<Block, AccountId, Hash, Moment>,
+    AccountId: Codec,
+    Hash: Codec,
+    Moment: Codec,
+{
+    fn verify(
+        &self,
+        lookup: CredentialLookup<Hash>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<CredentialVerification<AccountId, Hash, Moment>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.verify_credential(&at, lookup).map_err(|e| RpcError {
+            code: ErrorCode::ServerError(RUNTIME_ERROR),
+            message: "Unable to verify credential.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
+    fn status_list(
+        &self,
+        id: StatusListId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<EncodedStatusList<AccountId, Hash>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let snapshot = api.status_list(&at, id).map_err(|e| RpcError {
+            code: ErrorCode::ServerError(RUNTIME_ERROR),
+            message: "Unable to query status list.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })?;
+
+        snapshot
+            .map(|list| {
+                let encoded_list = encode_list(&list.bits).map_err(|e| RpcError {
+                    code: ErrorCode::ServerError(ENCODING_ERROR),
+                    message: "Unable to encode status list.".into(),
+                    data: Some(format!("{:?}", e).into()),
+                })?;
+                Ok(EncodedStatusList {
+                    issuer: list.issuer,
+                    status_purpose: list.purpose,
+                    length: list.length,
+                    encoded_list,
+                    hash: list.hash,
+                })
+            })
+            .transpose()
+    }
+}
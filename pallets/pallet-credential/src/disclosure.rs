@@ -0,0 +1,119 @@
+//! Selective disclosure of credential claims.
+//!
+//! The issuer hashes every claim of a credential together with a random salt and
+//! anchors the Merkle root of those hashes as the credential `claims_root`. The
+//! holder later reveals a subset of the claims, each with its salt and inclusion
+//! proof, and a verifier checks them against the anchored root. Undisclosed claims
+//! only ever appear as salted hashes, so they cannot be recovered by guessing.
+//!
+//! The tree is built like a batch tree: leaves and internal nodes are hashed with
+//! distinct prefixes, internal nodes hash their two children in ascending order
+//! and a node without a sibling is paired with itself.
+
+use crate::{hash_sorted_pair, merkle_leaf, merkle_root_from_proof, CredProperty};
+use frame_support::sp_runtime::traits::Hash as HashT;
+
+/// Random value mixed into a claim hash so that it can't be brute forced.
+pub type Salt = [u8; 32];
+
+/// A claim revealed by the holder, with what's needed to tie it to the claims root.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DisclosedClaim<Hash> {
+    pub claim: CredProperty,
+    pub salt: Salt,
+    pub proof: Vec<Hash>,
+}
+
+/// Salted hash of a single claim, a leaf of the claims tree.
+pub fn claim_hash<H: HashT>(claim: &CredProperty, salt: &Salt) -> H::Output {
+    H::hash_of(&(salt, claim))
+}
+
+/// Computes the claims root of salted claims, or `None` if there are no claims.
+pub fn claims_root<H>(claims: &[(CredProperty, Salt)]) -> Option<H::Output>
+where
+    H: HashT,
+    H::Output: Ord,
+{
+    let mut level = leaves::<H>(claims);
+    if level.is_empty() {
+        return None;
+    }
+    while level.len() > 1 {
+        level = next_level::<H>(&level);
+    }
+    level.pop()
+}
+
+/// Builds the disclosure of the claims at `indices`, or `None` if an index is out of range.
+pub fn disclose<H>(
+    claims: &[(CredProperty, Salt)],
+    indices: &[usize],
+) -> Option<Vec<DisclosedClaim<H::Output>>>
+where
+    H: HashT,
+    H::Output: Ord,
+{
+    let leaves = leaves::<H>(claims);
+    indices
+        .iter()
+        .map(|&index| {
+            let (claim, salt) = claims.get(index)?;
+            Some(DisclosedClaim {
+                claim: claim.clone(),
+                salt: *salt,
+                proof: inclusion_proof::<H>(&leaves, index),
+            })
+        })
+        .collect()
+}
+
+/// Returns true if every disclosed claim is part of the credential anchored with `root`.
+pub fn verify_disclosure<H>(disclosed: &[DisclosedClaim<H::Output>], root: &H::Output) -> bool
+where
+    H: HashT,
+    H::Output: Ord,
+{
+    !disclosed.is_empty()
+        && disclosed.iter().all(|item| {
+            let leaf = merkle_leaf::<H>(claim_hash::<H>(&item.claim, &item.salt).as_ref());
+            merkle_root_from_proof::<H>(leaf, &item.proof) == *root
+        })
+}
+
+fn leaves<H: HashT>(claims: &[(CredProperty, Salt)]) -> Vec<H::Output> {
+    claims
+        .iter()
+        .map(|(claim, salt)| merkle_leaf::<H>(claim_hash::<H>(claim, salt).as_ref()))
+        .collect()
+}
+
+fn next_level<H>(level: &[H::Output]) -> Vec<H::Output>
+where
+    H: HashT,
+    H::Output: Ord,
+{
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_sorted_pair::<H>(*left, *right),
+            _ => hash_sorted_pair::<H>(pair[0], pair[0]),
+        })
+        .collect()
+}
+
+// Sibling hashes from the leaf at `index` up to the root. Unpaired nodes are their own sibling.
+fn inclusion_proof<H>(leaves: &[H::Output], mut index: usize) -> Vec<H::Output>
+where
+    H: HashT,
+    H::Output: Ord,
+{
+    let mut proof = Vec::new();
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        proof.push(*level.get(index ^ 1).unwrap_or(&level[index]));
+        level = next_level::<H>(&level);
+        index /= 2;
+    }
+    proof
+}
@@ -6,9 +6,16 @@ use codec::{Decode, Encode};
 use core::result::Result;
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, dispatch, ensure, sp_runtime::RuntimeDebug,
-    sp_std::prelude::*, traits::EnsureOrigin,
+    sp_runtime::traits::{Hash as HashT, Zero}, sp_std::{prelude::*, vec}, traits::{EnsureOrigin, Get},
+    weights::Weight,
 };
 use frame_system::{self as system, ensure_signed};
+use pallet_schema::SchemaId;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+pub mod disclosure;
 
 #[cfg(test)]
 mod mock;
@@ -18,12 +25,28 @@ mod tests;
 
 // General constraints to limit data size
 pub const CRED_ID_MAX_LENGTH: usize = 24;
-pub const CRED_SUBJ_MAX_LENGTH: usize = 48;
-pub const CRED_MAX_PROPS: usize = 1;
+pub const STATUS_LIST_ID_MAX_LENGTH: usize = 24;
+pub const BATCH_ID_MAX_LENGTH: usize = 24;
+pub const BATCH_MAX_CREDENTIALS: u32 = 1_000_000;
+pub const PRESENTATION_PURPOSE_MAX_LENGTH: usize = 64;
+// Bounds the work of `on_initialize` at any block.
+pub const MAX_REINSTATEMENTS_PER_BLOCK: usize = 100;
+pub const MAX_CONSENT_EXPIRIES_PER_BLOCK: usize = 100;
+// 2^20 entries (128 KiB). StatusList2021 recommends at least 2^17 for herd privacy.
+pub const STATUS_LIST_MAX_LENGTH: u32 = 1_048_576;
+// Weight of writing a status list, per byte of its bitstring, and of setting one entry.
+pub const STATUS_LIST_WEIGHT_PER_BYTE: Weight = 1_000;
+pub const STATUS_LIST_WEIGHT_PER_ENTRY: Weight = 100;
+// Weight of verifying an sr25519 signature, about 50µs.
+pub const SIGNATURE_VERIFY_WEIGHT: Weight = 50_000_000;
 
 // Custom types
 pub type CredId = Vec<u8>;
-pub type CredSubject = Vec<u8>;
+pub type PropKey = Vec<u8>;
+pub type ReasonCode = u16;
+pub type StatusListId = Vec<u8>;
+pub type BatchId = Vec<u8>;
+pub type PresentationPurpose = Vec<u8>;
 
 // Credential contains master data (aka class-level) about a credential item.
 // This data is typically registered when a credential is signed, and remains static.
@@ -32,30 +55,481 @@ pub struct Credential<AccountId, Hash, Moment> {
     id: CredId,
     owner: AccountId,
     hash: Hash,
+    claims_root: Option<Hash>,
+    schema_id: SchemaId,
+    holder: Option<AccountId>,
     props: Option<Vec<CredProperty>>,
     registered: Moment,
+    status: CredentialStatus,
+    valid_from: Option<Moment>,
+    valid_until: Option<Moment>,
+    signer: AccountId,
+}
+
+impl<AccountId, Hash, Moment> Credential<AccountId, Hash, Moment>
+where
+    Moment: PartialOrd,
+{
+    pub fn status(&self) -> CredentialStatus {
+        self.status
+    }
+
+    pub fn holder(&self) -> Option<&AccountId> {
+        self.holder.as_ref()
+    }
+
+    /// Root over the salted claim hashes, used to verify selectively disclosed claims.
+    pub fn claims_root(&self) -> Option<&Hash> {
+        self.claims_root.as_ref()
+    }
+
+    /// The issuer key (the issuer DID or one of its signing delegates) that signed the credential.
+    pub fn signer(&self) -> &AccountId {
+        &self.signer
+    }
+
+    /// Returns true if the credential is active and `now` falls within its validity window.
+    pub fn is_valid_at(&self, now: &Moment) -> bool {
+        self.status == CredentialStatus::Active
+            && self.valid_from.as_ref().map_or(true, |from| now >= from)
+            && self.valid_until.as_ref().map_or(true, |until| now < until)
+    }
+}
+
+// Lifecycle state of a credential. Credentials are never removed from the
+// registry, so verifiers can tell a revoked credential from an unknown one.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum CredentialStatus {
+    Active,
+    Revoked,
+    Suspended,
+}
+
+impl Default for CredentialStatus {
+    fn default() -> Self {
+        CredentialStatus::Active
+    }
+}
+
+// How a verifier refers to the credential it wants checked.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum CredentialLookup<Hash> {
+    Id(CredId),
+    Hash(Hash),
+}
+
+// Answer to a verification query: who anchored the credential, when, under
+// which schema, and whether it is valid right now.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CredentialVerification<AccountId, Hash, Moment> {
+    pub id: CredId,
+    pub issuer: AccountId,
+    pub signer: AccountId,
+    pub claims_root: Option<Hash>,
+    pub schema_id: SchemaId,
+    pub registered: Moment,
+    pub status: CredentialStatus,
+    pub valid: bool,
+}
+
+// Answer of a holder to a credential issued to them.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum HolderResponse {
+    Acknowledged,
+    Rejected,
+}
+
+// Revocation keeps an audit record of who revoked a credential, when and why.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Revocation<AccountId, BlockNumber, Moment> {
+    by: AccountId,
+    block: BlockNumber,
+    timestamp: Moment,
+    reason: Option<ReasonCode>,
+}
+
+impl<AccountId, BlockNumber, Moment> Revocation<AccountId, BlockNumber, Moment> {
+    pub fn new(by: AccountId, block: BlockNumber, timestamp: Moment, reason: Option<ReasonCode>) -> Self {
+        Self {
+            by,
+            block,
+            timestamp,
+            reason,
+        }
+    }
+
+    pub fn by(&self) -> &AccountId {
+        &self.by
+    }
+
+    pub fn block(&self) -> &BlockNumber {
+        &self.block
+    }
+
+    pub fn timestamp(&self) -> &Moment {
+        &self.timestamp
+    }
+
+    pub fn reason(&self) -> Option<ReasonCode> {
+        self.reason
+    }
+}
+
+// Presentation records that a verifier checked a credential with the consent
+// of its holder, so holders can audit who verified their credentials.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Presentation<AccountId, Signature, Moment> {
+    cred_id: CredId,
+    verifier: AccountId,
+    consent: Signature,
+    purpose: PresentationPurpose,
+    timestamp: Moment,
+}
+
+impl<AccountId, Signature, Moment> Presentation<AccountId, Signature, Moment> {
+    pub fn new(
+        cred_id: CredId,
+        verifier: AccountId,
+        consent: Signature,
+        purpose: PresentationPurpose,
+        timestamp: Moment,
+    ) -> Self {
+        Self {
+            cred_id,
+            verifier,
+            consent,
+            purpose,
+            timestamp,
+        }
+    }
+
+    pub fn cred_id(&self) -> &[u8] {
+        self.cred_id.as_ref()
+    }
+
+    pub fn verifier(&self) -> &AccountId {
+        &self.verifier
+    }
+
+    pub fn consent(&self) -> &Signature {
+        &self.consent
+    }
+
+    pub fn purpose(&self) -> &[u8] {
+        self.purpose.as_ref()
+    }
+
+    pub fn timestamp(&self) -> &Moment {
+        &self.timestamp
+    }
+}
+
+// StatusChange is one entry of the status history kept for every credential.
+// Changes applied automatically by the runtime have no `by` account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct StatusChange<AccountId, BlockNumber, Moment> {
+    status: CredentialStatus,
+    by: Option<AccountId>,
+    block: BlockNumber,
+    timestamp: Moment,
+    reason: Option<ReasonCode>,
+}
+
+impl<AccountId, BlockNumber, Moment> StatusChange<AccountId, BlockNumber, Moment> {
+    pub fn new(
+        status: CredentialStatus,
+        by: Option<AccountId>,
+        block: BlockNumber,
+        timestamp: Moment,
+        reason: Option<ReasonCode>,
+    ) -> Self {
+        Self {
+            status,
+            by,
+            block,
+            timestamp,
+            reason,
+        }
+    }
+
+    pub fn status(&self) -> CredentialStatus {
+        self.status
+    }
+
+    pub fn by(&self) -> Option<&AccountId> {
+        self.by.as_ref()
+    }
+
+    pub fn block(&self) -> &BlockNumber {
+        &self.block
+    }
+
+    pub fn reason(&self) -> Option<ReasonCode> {
+        self.reason
+    }
+}
+
+// Purpose of a status list, mirroring the StatusList2021 `statusPurpose`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum StatusPurpose {
+    Revocation,
+    Suspension,
+}
+
+// StatusList anchors a fixed-length bitstring where a set bit marks the credential
+// at that index as revoked or suspended. Bits are packed most significant bit first,
+// as required by StatusList2021; GZIP and base64 encoding are applied by the RPC.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct StatusList<AccountId, Moment> {
+    issuer: AccountId,
+    purpose: StatusPurpose,
+    length: u32,
+    bits: Vec<u8>,
+    updated: Moment,
+}
+
+impl<AccountId, Moment> StatusList<AccountId, Moment> {
+    pub fn new(issuer: AccountId, purpose: StatusPurpose, length: u32, created: Moment) -> Self {
+        Self {
+            issuer,
+            purpose,
+            length,
+            bits: vec![0u8; ((length + 7) / 8) as usize],
+            updated: created,
+        }
+    }
+
+    pub fn issuer(&self) -> &AccountId {
+        &self.issuer
+    }
+
+    pub fn purpose(&self) -> StatusPurpose {
+        self.purpose
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    pub fn bits(&self) -> &[u8] {
+        self.bits.as_ref()
+    }
+
+    pub fn get(&self, index: u32) -> Option<bool> {
+        if index >= self.length {
+            return None;
+        }
+        Some(self.bits[(index / 8) as usize] & (0x80 >> (index % 8)) != 0)
+    }
+
+    // Sets the bits in `start..end`. Callers validate the range first.
+    fn set_range(&mut self, start: u32, end: u32, value: bool) {
+        for index in start..end {
+            let mask = 0x80 >> (index % 8);
+            let byte = &mut self.bits[(index / 8) as usize];
+            if value {
+                *byte |= mask;
+            } else {
+                *byte &= !mask;
+            }
+        }
+    }
+}
+
+// Status list served to verifiers through the `CredentialApi` runtime API: the packed
+// bitstring and its hash. The RPC applies the GZIP and base64 encoding of StatusList2021.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct StatusListSnapshot<AccountId, Hash> {
+    pub issuer: AccountId,
+    pub purpose: StatusPurpose,
+    pub length: u32,
+    pub bits: Vec<u8>,
+    pub hash: Hash,
+}
+
+// Weight of rewriting a status list of `length` entries after setting `entries` of them.
+fn status_list_weight<T: Trait>(length: u32, entries: u32) -> Weight {
+    let bytes = (length as Weight + 7) / 8;
+    T::DbWeight::get()
+        .reads_writes(2, 1)
+        .saturating_add(STATUS_LIST_WEIGHT_PER_BYTE.saturating_mul(bytes))
+        .saturating_add(STATUS_LIST_WEIGHT_PER_ENTRY.saturating_mul(entries as Weight))
+}
+
+// Weight of an extrinsic checking one signature and doing `reads` and `writes` DB operations.
+fn signed_weight<T: Trait>(reads: Weight, writes: Weight) -> Weight {
+    T::DbWeight::get()
+        .reads_writes(reads, writes)
+        .saturating_add(SIGNATURE_VERIFY_WEIGHT)
+}
+
+// CredentialBatch anchors the Merkle root over the hashes of many credentials
+// issued together, instead of storing every credential individually.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct CredentialBatch<AccountId, Hash, Moment> {
+    issuer: AccountId,
+    schema_id: SchemaId,
+    root: Hash,
+    count: u32,
+    registered: Moment,
+}
+
+impl<AccountId, Hash, Moment> CredentialBatch<AccountId, Hash, Moment> {
+    pub fn new(issuer: AccountId, schema_id: SchemaId, root: Hash, count: u32, registered: Moment) -> Self {
+        Self {
+            issuer,
+            schema_id,
+            root,
+            count,
+            registered,
+        }
+    }
+
+    pub fn issuer(&self) -> &AccountId {
+        &self.issuer
+    }
+
+    pub fn schema_id(&self) -> &[u8] {
+        self.schema_id.as_ref()
+    }
+
+    pub fn root(&self) -> &Hash {
+        &self.root
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+}
+
+// Prefixes separating leaf hashes from internal node hashes, so that an internal node
+// can never be presented as a leaf.
+const MERKLE_LEAF_PREFIX: u8 = 0x00;
+const MERKLE_NODE_PREFIX: u8 = 0x01;
+
+/// Hashes a value into a Merkle tree leaf.
+pub fn merkle_leaf<H: HashT>(value: &[u8]) -> H::Output {
+    let mut leaf = Vec::with_capacity(1 + value.len());
+    leaf.push(MERKLE_LEAF_PREFIX);
+    leaf.extend_from_slice(value);
+    H::hash(&leaf)
+}
+
+/// Computes the Merkle root reached from the `leaf` node by hashing it with each proof node in turn.
+/// Pairs are sorted before hashing, so proofs don't need to carry left/right positions.
+pub fn merkle_root_from_proof<H>(leaf: H::Output, proof: &[H::Output]) -> H::Output
+where
+    H: HashT,
+    H::Output: Ord,
+{
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_sorted_pair::<H>(node, *sibling))
+}
+
+/// Number of proof nodes from a leaf to the root of a tree over `count` leaves, `ceil(log2(count))`.
+pub fn merkle_proof_length(count: u32) -> usize {
+    (32 - count.saturating_sub(1).leading_zeros()) as usize
+}
+
+// Hashes the concatenation of two tree nodes in ascending order, behind the node prefix.
+pub(crate) fn hash_sorted_pair<H>(a: H::Output, b: H::Output) -> H::Output
+where
+    H: HashT,
+    H::Output: Ord,
+{
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    let mut pair = Vec::with_capacity(1 + first.as_ref().len() + second.as_ref().len());
+    pair.push(MERKLE_NODE_PREFIX);
+    pair.extend_from_slice(first.as_ref());
+    pair.extend_from_slice(second.as_ref());
+    H::hash(&pair)
 }
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
 pub struct CredProperty {
-    subject: CredSubject,
+    key: PropKey,
+    value: ClaimValue,
 }
 
 impl CredProperty {
-    pub fn new(subject: &[u8]) -> Self {
+    pub fn new(key: &[u8], value: ClaimValue) -> Self {
         Self {
-            subject: subject.to_vec(),
+            key: key.to_vec(),
+            value,
         }
     }
 
-    pub fn subject(&self) -> &[u8] {
-        self.subject.as_ref()
+    pub fn key(&self) -> &[u8] {
+        self.key.as_ref()
+    }
+
+    pub fn value(&self) -> &ClaimValue {
+        &self.value
     }
 }
 
-pub trait Trait: frame_system::Trait + timestamp::Trait {
+// Typed value of a credential claim. A claim that must stay private can be
+// anchored as the hash of its value only, and disclosed off-chain.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum ClaimValue {
+    Text(Vec<u8>),
+    Integer(i64),
+    Boolean(bool),
+    Timestamp(u64),
+    Hashed([u8; 32]),
+}
+
+impl ClaimValue {
+    /// Size in bytes of the value, checked against `MaxPropValueLength`.
+    pub fn size(&self) -> usize {
+        match self {
+            ClaimValue::Text(text) => text.len(),
+            ClaimValue::Integer(_) | ClaimValue::Timestamp(_) => 8,
+            ClaimValue::Boolean(_) => 1,
+            ClaimValue::Hashed(_) => 32,
+        }
+    }
+
+    pub fn is_hashed(&self) -> bool {
+        matches!(self, ClaimValue::Hashed(_))
+    }
+}
+
+/// Decides whether an account may manage the credentials of an issuing organization.
+pub trait IssuerCheck<AccountId> {
+    fn is_issuer(issuer: &AccountId, who: &AccountId) -> bool;
+}
+
+/// Accepts the owner of the issuer DID and its DID delegates of type `D`.
+pub struct DelegateIssuerCheck<T, D>(core::marker::PhantomData<(T, D)>);
+impl<T: Trait, D: Get<&'static [u8]>> IssuerCheck<T::AccountId> for DelegateIssuerCheck<T, D> {
+    fn is_issuer(issuer: &T::AccountId, who: &T::AccountId) -> bool {
+        <did::Module<T>>::valid_delegate(issuer, D::get(), who).is_ok()
+    }
+}
+
+pub trait Trait: frame_system::Trait + timestamp::Trait + pallet_schema::Trait + did::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
-    type CreateRoleOrigin: EnsureOrigin<Self::Origin>;
+    /// Origin of organization members, resolving to the member and the organization it acts for.
+    type CreateRoleOrigin: EnsureOrigin<Self::Origin, Success = (Self::AccountId, Self::AccountId)>;
+    /// Check an account must pass to manage the credentials of an issuing organization.
+    type IssuerCheck: IssuerCheck<Self::AccountId>;
+    /// Maximum number of properties (claims) a credential can carry.
+    type MaxProps: Get<u32>;
+    /// Maximum length in bytes of a property key.
+    type MaxPropKeyLength: Get<u32>;
+    /// Maximum size in bytes of a property value.
+    type MaxPropValueLength: Get<u32>;
 }
 
 decl_storage! {
@@ -64,6 +538,22 @@ decl_storage! {
     pub CredsOfOrganization get(fn cred_of_org): map hasher(blake2_128_concat) T::AccountId => Vec<CredId>;
     pub IssuerOfCred get(fn issuer_of_cred): map hasher(blake2_128_concat) CredId => Option<T::AccountId>;
     pub CredByHash get(fn cred_by_hash):map hasher(opaque_blake2_256) T::Hash => Vec<CredId>;
+    pub CredsOfSchema get(fn credentials_of_schema): map hasher(blake2_128_concat) SchemaId => Vec<CredId>;
+    pub CredsOfHolder get(fn credentials_of_holder): map hasher(blake2_128_concat) T::AccountId => Vec<CredId>;
+    pub HolderResponses get(fn holder_response): map hasher(blake2_128_concat) CredId => Option<HolderResponse>;
+    pub Revocations get(fn revocation_of): map hasher(blake2_128_concat) CredId => Option<Revocation<T::AccountId, T::BlockNumber, T::Moment>>;
+    pub StatusHistory get(fn status_history): map hasher(blake2_128_concat) CredId => Vec<StatusChange<T::AccountId, T::BlockNumber, T::Moment>>;
+    pub ReinstateAt get(fn reinstate_at): map hasher(blake2_128_concat) CredId => Option<T::BlockNumber>;
+    pub ReinstatementsDue get(fn reinstatements_due): map hasher(twox_64_concat) T::BlockNumber => Vec<CredId>;
+    pub StatusLists get(fn status_list): map hasher(blake2_128_concat) StatusListId => Option<StatusList<T::AccountId, T::Moment>>;
+    pub Batches get(fn batch_by_id): map hasher(blake2_128_concat) BatchId => Option<CredentialBatch<T::AccountId, T::Hash, T::Moment>>;
+    pub BatchesOfOrganization get(fn batches_of_org): map hasher(blake2_128_concat) T::AccountId => Vec<BatchId>;
+    pub HolderHistory get(fn holder_history): map hasher(blake2_128_concat) CredId => Vec<(T::AccountId, T::Moment)>;
+    pub ReplacedBy get(fn replaced_by): map hasher(blake2_128_concat) CredId => Option<CredId>;
+    pub Replaces get(fn replaces): map hasher(blake2_128_concat) CredId => Option<CredId>;
+    pub PresentationsOfHolder get(fn presentations_of_holder): map hasher(blake2_128_concat) T::AccountId => Vec<Presentation<T::AccountId, <T as did::Trait>::Signature, T::Moment>>;
+    pub UsedConsents get(fn consent_used): map hasher(identity) T::Hash => bool;
+    pub ConsentsExpiring get(fn consents_expiring): map hasher(twox_64_concat) T::BlockNumber => Vec<T::Hash>;
     }
 }
 
@@ -72,9 +562,21 @@ decl_event!(
     where
         AccountId = <T as system::Trait>::AccountId,
         Hash = <T as frame_system::Trait>::Hash,
+        BlockNumber = <T as system::Trait>::BlockNumber,
     {
         CredentialRegistered(AccountId, CredId, Hash),
         CredentialRevoked(AccountId, CredId, Hash),
+        CredentialSuspended(AccountId, CredId, Option<BlockNumber>),
+        CredentialReinstated(AccountId, CredId),
+        CredentialAutoReinstated(CredId),
+        StatusListCreated(AccountId, StatusListId, u32),
+        StatusListUpdated(AccountId, StatusListId, Hash),
+        BatchRegistered(AccountId, BatchId, Hash, u32),
+        CredentialAcknowledged(AccountId, CredId),
+        CredentialRejected(AccountId, CredId),
+        PresentationRecorded(AccountId, CredId, AccountId),
+        CredentialTransferred(AccountId, CredId, AccountId),
+        CredentialReissued(AccountId, CredId, CredId),
     }
 );
 
@@ -83,8 +585,44 @@ decl_error! {
     CredIdMissing,
     CredIdTooLong,
     CredIdExists,
-    CredInvalidSubject,
     CredTooManyProps,
+    CredInvalidPropKey,
+    CredPropValueTooLong,
+    CredDuplicatePropKey,
+    CredPropNotInSchema,
+    CredSchemaMissing,
+    CredSchemaInactive,
+    CredDetailsMismatch,
+    CredAlreadyRevoked,
+    CredNotIssuer,
+    CredNotActingOrganization,
+    CredNotActive,
+    CredNotSuspended,
+    CredInvalidReinstateBlock,
+    CredReinstateBlockFull,
+    CredInvalidValidityPeriod,
+    CredAlreadyExpired,
+    CredPropsWithClaimsRoot,
+    StatusListIdMissing,
+    StatusListIdTooLong,
+    StatusListIdExists,
+    StatusListInvalidLength,
+    StatusListIndexOutOfRange,
+    BatchIdMissing,
+    BatchIdTooLong,
+    BatchIdExists,
+    BatchInvalidCount,
+    CredNotHolder,
+    CredHolderResponded,
+    CredBadSignature,
+    CredHolderMissing,
+    PresentationPurposeTooLong,
+    PresentationBadConsent,
+    PresentationConsentExpired,
+    PresentationConsentUsed,
+    PresentationConsentBlockFull,
+    CredNotTransferable,
+    CredSameHolder,
     }
 }
 
@@ -93,64 +631,390 @@ decl_module! {
     type Error = Error<T>;
     fn deposit_event() = default;
 
-    #[weight = 10]
+    const MaxProps: u32 = T::MaxProps::get();
+    const MaxPropKeyLength: u32 = T::MaxPropKeyLength::get();
+    const MaxPropValueLength: u32 = T::MaxPropValueLength::get();
+
+    fn on_initialize(n: T::BlockNumber) -> Weight {
+        // Reinstate suspensions which reached their scheduled block
+        let due = <ReinstatementsDue<T>>::take(n);
+        let mut reinstated: Weight = 0;
+        for id in due.iter() {
+            // Skip schedules cancelled by a manual reinstatement or revocation
+            if Self::reinstate_at(id) != Some(n) {
+                continue;
+            }
+            if let Some(credential) = Self::cred_by_id(id) {
+                if credential.status == CredentialStatus::Suspended {
+                    Self::change_status(id, credential, CredentialStatus::Active, None, None);
+                    Self::deposit_event(RawEvent::CredentialAutoReinstated(id.clone()));
+                    reinstated += 1;
+                }
+            }
+            <ReinstateAt<T>>::remove(id);
+        }
+
+        // Forget used consents once they expired, they can't be replayed anymore
+        let expired = <ConsentsExpiring<T>>::take(n);
+        for consent_key in expired.iter() {
+            <UsedConsents<T>>::remove(consent_key);
+        }
+
+        T::DbWeight::get().reads_writes(
+            2 + 2 * due.len() as Weight,
+            2 + due.len() as Weight + 2 * reinstated + expired.len() as Weight,
+        )
+    }
+
+    #[weight = signed_weight::<T>(6, 6)]
     pub fn register_credential(origin, id: CredId, owner: T::AccountId, hash: T::Hash,
-        props: Option<Vec<CredProperty>>) -> dispatch::DispatchResult {
+        claims_root: Option<T::Hash>, schema_id: SchemaId, holder: Option<T::AccountId>, props: Option<Vec<CredProperty>>,
+        valid_from: Option<T::Moment>, valid_until: Option<T::Moment>, signer: T::AccountId,
+        signature: <T as did::Trait>::Signature) -> dispatch::DispatchResult {
 
-        T::CreateRoleOrigin::ensure_origin(origin.clone())?;
-        let who = ensure_signed(origin)?;
+        let (who, org) = <T as Trait>::CreateRoleOrigin::ensure_origin(origin)?;
+
+        // Credentials are issued by the organization the member acts for
+        ensure!(owner == org, Error::<T>::CredNotActingOrganization);
+
+        // Validate and build the credential (5-6 DB reads)
+        let credential = Self::prepare_credential(id.clone(), owner, hash, claims_root, schema_id,
+            holder, props, valid_from, valid_until, signer, signature)?;
+
+        // Add Cred, ownerOf, schema & holder indexes (5-6 DB writes)
+        Self::insert_credential(credential);
+
+        Self::deposit_event(RawEvent::CredentialRegistered(who, id, hash));
+
+        Ok(())
+    }
+
+    #[weight = 100]
+    pub fn revoke_credential(origin, id: CredId, owner: T::AccountId, hash: T::Hash,
+        reason: Option<ReasonCode>) -> dispatch::DispatchResult {
+
+        let (who, org) = <T as Trait>::CreateRoleOrigin::ensure_origin(origin)?;
 
         // Validate Cred ID
         Self::validate_cred_id(&id)?;
 
-        // Validate credential props
-        Self::validate_cred_props(&props)?;
+        // Check credential exists and matches the given details (1 DB read)
+        let credential = Self::cred_by_id(&id).ok_or(Error::<T>::CredIdMissing)?;
+        ensure!(
+            credential.owner == owner && credential.hash == hash,
+            Error::<T>::CredDetailsMismatch
+        );
 
-        // Check credential doesn't exist yet (1 DB read)
-        Self::validate_new_credential(&id)?;
+        // Only the issuer or one of its delegates may revoke (1 DB read)
+        Self::validate_acting_issuer(&credential.owner, &who, &org)?;
 
-        // Create a credential instance
-        let credential = Self::new_credential()
-        .identified_by(id.clone())
-        .owned_by(owner.clone())
-        .credential_hash(hash.clone())
-        .registered_on(<timestamp::Module<T>>::now())
-        .with_props(props)
-        .build();
+        ensure!(
+            credential.status != CredentialStatus::Revoked,
+            Error::<T>::CredAlreadyRevoked
+        );
 
-        // Add Cred & ownerOf (4 DB writes)
-        <Credentials<T>>::insert(&id, credential);
-        <CredsOfOrganization<T>>::append(&owner, &id);
-        <IssuerOfCred<T>>::insert(&id, &owner);
-        <CredByHash<T>>::append(&hash, &id);
+        // Keep the credential and its indexes, record the revocation (4 DB writes)
+        Self::revoke(&id, credential, who.clone(), reason);
 
-        Self::deposit_event(RawEvent::CredentialRegistered(who, id, hash));
+        Self::deposit_event(RawEvent::CredentialRevoked(who, id, hash));
 
         Ok(())
     }
 
     #[weight = 100]
-    pub fn revoke_credential(origin, id: CredId, owner: T::AccountId, hash: T::Hash) -> dispatch::DispatchResult {
+    pub fn suspend_credential(origin, id: CredId, reason: Option<ReasonCode>,
+        reinstate_at: Option<T::BlockNumber>) -> dispatch::DispatchResult {
+
+        let (who, org) = <T as Trait>::CreateRoleOrigin::ensure_origin(origin)?;
+
+        // Validate Cred ID
+        Self::validate_cred_id(&id)?;
+
+        // Check credential exists and the caller may manage it (2 DB reads)
+        let credential = Self::cred_by_id(&id).ok_or(Error::<T>::CredIdMissing)?;
+        Self::validate_acting_issuer(&credential.owner, &who, &org)?;
+
+        ensure!(
+            credential.status == CredentialStatus::Active,
+            Error::<T>::CredNotActive
+        );
+        if let Some(block) = reinstate_at {
+            ensure!(
+                block > <system::Module<T>>::block_number(),
+                Error::<T>::CredInvalidReinstateBlock
+            );
+            // Check the block has room for another reinstatement (1 DB read)
+            ensure!(
+                Self::reinstatements_due(block).len() < MAX_REINSTATEMENTS_PER_BLOCK,
+                Error::<T>::CredReinstateBlockFull
+            );
+        }
+
+        // Suspend and optionally schedule the reinstatement (2-4 DB writes)
+        Self::change_status(&id, credential, CredentialStatus::Suspended, Some(who.clone()), reason);
+        if let Some(block) = reinstate_at {
+            <ReinstateAt<T>>::insert(&id, block);
+            <ReinstatementsDue<T>>::append(block, &id);
+        }
+
+        Self::deposit_event(RawEvent::CredentialSuspended(who, id, reinstate_at));
+
+        Ok(())
+    }
+
+    #[weight = 100]
+    pub fn reinstate_credential(origin, id: CredId) -> dispatch::DispatchResult {
+
+        let (who, org) = <T as Trait>::CreateRoleOrigin::ensure_origin(origin)?;
+
+        // Validate Cred ID
+        Self::validate_cred_id(&id)?;
+
+        // Check credential exists and the caller may manage it (2 DB reads)
+        let credential = Self::cred_by_id(&id).ok_or(Error::<T>::CredIdMissing)?;
+        Self::validate_acting_issuer(&credential.owner, &who, &org)?;
+
+        ensure!(
+            credential.status == CredentialStatus::Suspended,
+            Error::<T>::CredNotSuspended
+        );
+
+        // Reactivate and cancel any scheduled reinstatement (3 DB writes)
+        Self::change_status(&id, credential, CredentialStatus::Active, Some(who.clone()), None);
+        <ReinstateAt<T>>::remove(&id);
 
-        T::CreateRoleOrigin::ensure_origin(origin.clone())?;
+        Self::deposit_event(RawEvent::CredentialReinstated(who, id));
+
+        Ok(())
+    }
+
+    #[weight = 10]
+    pub fn acknowledge_credential(origin, id: CredId) -> dispatch::DispatchResult {
+        let who = ensure_signed(origin)?;
+
+        Self::respond_as_holder(&who, &id, HolderResponse::Acknowledged)?;
+
+        Self::deposit_event(RawEvent::CredentialAcknowledged(who, id));
+
+        Ok(())
+    }
+
+    #[weight = 10]
+    pub fn reject_credential(origin, id: CredId) -> dispatch::DispatchResult {
         let who = ensure_signed(origin)?;
 
+        Self::respond_as_holder(&who, &id, HolderResponse::Rejected)?;
+
+        Self::deposit_event(RawEvent::CredentialRejected(who, id));
+
+        Ok(())
+    }
+
+    #[weight = 10]
+    pub fn transfer_credential(origin, id: CredId, new_holder: T::AccountId) -> dispatch::DispatchResult {
+        let who = ensure_signed(origin)?;
+
+        Self::validate_cred_id(&id)?;
+
+        // Check the caller owns the current holder DID (2 DB reads)
+        let mut credential = Self::cred_by_id(&id).ok_or(Error::<T>::CredIdMissing)?;
+        let holder = credential.holder().cloned().ok_or(Error::<T>::CredHolderMissing)?;
+        ensure!(
+            <did::Module<T>>::is_owner(&holder, &who).is_ok(),
+            Error::<T>::CredNotHolder
+        );
+        ensure!(new_holder != holder, Error::<T>::CredSameHolder);
+
+        // Only active credentials of transferable schemas can move (1 DB read)
+        ensure!(
+            credential.status == CredentialStatus::Active,
+            Error::<T>::CredNotActive
+        );
+        ensure!(
+            <pallet_schema::Module<T>>::schema_transferable(&credential.schema_id),
+            Error::<T>::CredNotTransferable
+        );
+
+        // Move the credential, keep the previous holder in its provenance (5 DB writes)
+        credential.holder = Some(new_holder.clone());
+        <Credentials<T>>::insert(&id, credential);
+        <CredsOfHolder<T>>::mutate(&holder, |ids| ids.retain(|held| *held != id));
+        <CredsOfHolder<T>>::append(&new_holder, &id);
+        <HolderHistory<T>>::append(&id, (holder.clone(), <timestamp::Module<T>>::now()));
+        // The new holder answers the credential afresh
+        <HolderResponses>::remove(&id);
+
+        Self::deposit_event(RawEvent::CredentialTransferred(holder, id, new_holder));
+
+        Ok(())
+    }
+
+    #[weight = signed_weight::<T>(8, 12)]
+    pub fn reissue_credential(origin, id: CredId, new_id: CredId, hash: T::Hash,
+        claims_root: Option<T::Hash>, holder: Option<T::AccountId>, props: Option<Vec<CredProperty>>,
+        valid_from: Option<T::Moment>, valid_until: Option<T::Moment>, signer: T::AccountId,
+        signature: <T as did::Trait>::Signature, reason: Option<ReasonCode>) -> dispatch::DispatchResult {
+
+        let (who, org) = <T as Trait>::CreateRoleOrigin::ensure_origin(origin)?;
+
         // Validate Cred ID
         Self::validate_cred_id(&id)?;
 
-        // Check credential doesn't exist yet (1 DB read)
-        Self::validate_credential_exists(&id)?;
+        // Only the issuer or one of its delegates may reissue (2 DB reads)
+        let credential = Self::cred_by_id(&id).ok_or(Error::<T>::CredIdMissing)?;
+        Self::validate_acting_issuer(&credential.owner, &who, &org)?;
+        ensure!(
+            credential.status != CredentialStatus::Revoked,
+            Error::<T>::CredAlreadyRevoked
+        );
 
-        // Remove Cred & ownerOf (4 DB writes)
-        <Credentials<T>>::remove(&id);
-        <CredsOfOrganization<T>>::remove(&owner);
-        <IssuerOfCred<T>>::remove(&id);
-        <CredByHash<T>>::remove(&hash);
+        // Validate the replacement, issued by the same organization under the same
+        // schema, before touching storage (5-6 DB reads)
+        let replacement = Self::prepare_credential(new_id.clone(), credential.owner.clone(), hash,
+            claims_root, credential.schema_id.clone(), holder, props, valid_from, valid_until,
+            signer, signature)?;
 
-        Self::deposit_event(RawEvent::CredentialRevoked(who, id, hash));
+        // Register the replacement, revoke the old credential and link both (11-12 DB writes)
+        Self::insert_credential(replacement);
+        Self::revoke(&id, credential, who.clone(), reason);
+        <ReplacedBy>::insert(&id, &new_id);
+        <Replaces>::insert(&new_id, &id);
+
+        Self::deposit_event(RawEvent::CredentialReissued(who, id, new_id));
 
         Ok(())
     }
+
+    #[weight = signed_weight::<T>(4, 3)]
+    pub fn record_presentation(origin, id: CredId, purpose: PresentationPurpose, expires: T::BlockNumber,
+        consent: <T as did::Trait>::Signature) -> dispatch::DispatchResult {
+        let verifier = ensure_signed(origin)?;
+
+        Self::validate_cred_id(&id)?;
+        ensure!(
+            purpose.len() <= PRESENTATION_PURPOSE_MAX_LENGTH,
+            Error::<T>::PresentationPurposeTooLong
+        );
+        ensure!(
+            expires > <system::Module<T>>::block_number(),
+            Error::<T>::PresentationConsentExpired
+        );
+
+        // Check the credential exists and was issued to a holder (1 DB read)
+        let credential = Self::cred_by_id(&id).ok_or(Error::<T>::CredIdMissing)?;
+        let holder = credential.holder().cloned().ok_or(Error::<T>::CredHolderMissing)?;
+
+        // Check the current owner of the holder DID consented (1 DB read)
+        let payload = Self::presentation_consent_payload(&id, &verifier, &purpose, &expires);
+        <did::Module<T>>::check_signature(&consent, &payload, &<did::Module<T>>::identity_owner(&holder))
+            .map_err(|_| Error::<T>::PresentationBadConsent)?;
+
+        // Check the consent wasn't used before and can be forgotten at its expiry (2 DB reads)
+        let consent_key = T::Hashing::hash(&payload);
+        ensure!(
+            !Self::consent_used(&consent_key),
+            Error::<T>::PresentationConsentUsed
+        );
+        ensure!(
+            Self::consents_expiring(expires).len() < MAX_CONSENT_EXPIRIES_PER_BLOCK,
+            Error::<T>::PresentationConsentBlockFull
+        );
+
+        // Mark the consent used until it expires and log the presentation for the holder (3 DB writes)
+        <UsedConsents<T>>::insert(consent_key, true);
+        <ConsentsExpiring<T>>::append(expires, consent_key);
+        let now = <timestamp::Module<T>>::now();
+        let presentation = Presentation::new(id.clone(), verifier.clone(), consent, purpose, now);
+        <PresentationsOfHolder<T>>::append(&holder, presentation);
+
+        Self::deposit_event(RawEvent::PresentationRecorded(verifier, id, holder));
+
+        Ok(())
+    }
+
+    #[weight = 10]
+    pub fn register_credential_batch(origin, id: BatchId, owner: T::AccountId, root: T::Hash,
+        schema_id: SchemaId, count: u32) -> dispatch::DispatchResult {
+
+        let (who, org) = <T as Trait>::CreateRoleOrigin::ensure_origin(origin)?;
+
+        // Validate batch ID and size
+        Self::validate_batch_id(&id)?;
+        ensure!(
+            count > 0 && count <= BATCH_MAX_CREDENTIALS,
+            Error::<T>::BatchInvalidCount
+        );
+
+        // Only the issuer or one of its delegates may anchor batches (1 DB read)
+        Self::validate_acting_issuer(&owner, &who, &org)?;
+
+        // Check batch doesn't exist yet (1 DB read)
+        ensure!(
+            !<Batches<T>>::contains_key(&id),
+            Error::<T>::BatchIdExists
+        );
+
+        // Check the referenced schema is registered and active (2 DB reads)
+        Self::validate_cred_schema(&schema_id)?;
+
+        // Anchor the root & batch metadata (2 DB writes)
+        let batch = CredentialBatch::new(
+            owner.clone(),
+            schema_id,
+            root,
+            count,
+            <timestamp::Module<T>>::now(),
+        );
+        <Batches<T>>::insert(&id, batch);
+        <BatchesOfOrganization<T>>::append(&owner, &id);
+
+        Self::deposit_event(RawEvent::BatchRegistered(who, id, root, count));
+
+        Ok(())
+    }
+
+    #[weight = status_list_weight::<T>(*length, 0)]
+    pub fn create_status_list(origin, id: StatusListId, owner: T::AccountId, purpose: StatusPurpose,
+        length: u32) -> dispatch::DispatchResult {
+
+        let (who, org) = <T as Trait>::CreateRoleOrigin::ensure_origin(origin)?;
+
+        // Validate status list ID and size
+        Self::validate_status_list_id(&id)?;
+        ensure!(
+            length > 0 && length <= STATUS_LIST_MAX_LENGTH,
+            Error::<T>::StatusListInvalidLength
+        );
+
+        // Only the issuer or one of its delegates may create lists (2 DB reads)
+        Self::validate_acting_issuer(&owner, &who, &org)?;
+        ensure!(
+            !<StatusLists<T>>::contains_key(&id),
+            Error::<T>::StatusListIdExists
+        );
+
+        // Every entry starts cleared (1 DB write)
+        let list = StatusList::new(owner, purpose, length, <timestamp::Module<T>>::now());
+        <StatusLists<T>>::insert(&id, list);
+
+        Self::deposit_event(RawEvent::StatusListCreated(who, id, length));
+
+        Ok(())
+    }
+
+    // Charged for the largest list, the unused weight is refunded.
+    #[weight = status_list_weight::<T>(STATUS_LIST_MAX_LENGTH, 1)]
+    pub fn update_status_list(origin, id: StatusListId, index: u32,
+        status: bool) -> dispatch::DispatchResultWithPostInfo {
+        Self::update_status_list_entries(origin, id, index, index.saturating_add(1), status)
+    }
+
+    // Charged for the largest list, the unused weight is refunded.
+    #[weight = status_list_weight::<T>(STATUS_LIST_MAX_LENGTH, end.saturating_sub(*start))]
+    pub fn update_status_list_range(origin, id: StatusListId, start: u32, end: u32,
+        status: bool) -> dispatch::DispatchResultWithPostInfo {
+        Self::update_status_list_entries(origin, id, start, end, status)
+    }
     }
 }
 
@@ -160,6 +1024,157 @@ impl<T: Trait> Module<T> {
         CredentialBuilder::<T::AccountId, T::Hash, T::Moment>::default()
     }
 
+    // Runs every registration check and builds the credential, without writing to storage.
+    #[allow(clippy::too_many_arguments)]
+    fn prepare_credential(
+        id: CredId,
+        owner: T::AccountId,
+        hash: T::Hash,
+        claims_root: Option<T::Hash>,
+        schema_id: SchemaId,
+        holder: Option<T::AccountId>,
+        props: Option<Vec<CredProperty>>,
+        valid_from: Option<T::Moment>,
+        valid_until: Option<T::Moment>,
+        signer: T::AccountId,
+        signature: <T as did::Trait>::Signature,
+    ) -> Result<Credential<T::AccountId, T::Hash, T::Moment>, Error<T>> {
+        // Validate Cred ID
+        Self::validate_cred_id(&id)?;
+
+        // Validate credential props
+        Self::validate_cred_props(&props)?;
+
+        // Claims anchored by a claims root are disclosed selectively, never in clear
+        ensure!(
+            claims_root.is_none() || props.is_none(),
+            Error::<T>::CredPropsWithClaimsRoot
+        );
+
+        // Validate the validity window against the current time
+        let now = <timestamp::Module<T>>::now();
+        Self::validate_cred_validity(&now, &valid_from, &valid_until)?;
+
+        // Check credential doesn't exist yet (1 DB read)
+        Self::validate_new_credential(&id)?;
+
+        // Check the referenced schema is registered and active (2 DB reads)
+        Self::validate_cred_schema(&schema_id)?;
+
+        // Check the property keys are declared by the schema (1 DB read)
+        Self::validate_props_against_schema(&schema_id, &props)?;
+
+        // Check the issuer signed the credential with a valid key (1-2 DB reads)
+        let payload = Self::credential_signing_payload(&id, &hash, &claims_root, &schema_id);
+        <did::Module<T>>::valid_signer(&owner, &signature, &payload, &signer)
+            .map_err(|_| Error::<T>::CredBadSignature)?;
+
+        Ok(Self::new_credential()
+            .identified_by(id)
+            .owned_by(owner)
+            .credential_hash(hash)
+            .with_claims_root(claims_root)
+            .with_schema(schema_id)
+            .held_by(holder)
+            .registered_on(now)
+            .with_props(props)
+            .valid_between(valid_from, valid_until)
+            .signed_by(signer)
+            .build())
+    }
+
+    // Stores a new credential with its organization, hash, schema and holder indexes.
+    fn insert_credential(credential: Credential<T::AccountId, T::Hash, T::Moment>) {
+        let id = credential.id.clone();
+        <CredsOfOrganization<T>>::append(&credential.owner, &id);
+        <IssuerOfCred<T>>::insert(&id, &credential.owner);
+        <CredByHash<T>>::append(&credential.hash, &id);
+        <CredsOfSchema>::append(&credential.schema_id, &id);
+        if let Some(holder) = &credential.holder {
+            <CredsOfHolder<T>>::append(holder, &id);
+        }
+        <Credentials<T>>::insert(&id, credential);
+    }
+
+    // Marks a credential revoked and keeps the audit record of the revocation.
+    fn revoke(
+        id: &[u8],
+        credential: Credential<T::AccountId, T::Hash, T::Moment>,
+        who: T::AccountId,
+        reason: Option<ReasonCode>,
+    ) {
+        let revocation = Revocation::new(
+            who.clone(),
+            <system::Module<T>>::block_number(),
+            <timestamp::Module<T>>::now(),
+            reason,
+        );
+        Self::change_status(id, credential, CredentialStatus::Revoked, Some(who), reason);
+        <Revocations<T>>::insert(id, revocation);
+        <ReinstateAt<T>>::remove(id);
+    }
+
+    // Updates the status of a credential and appends the change to its history.
+    fn change_status(
+        id: &[u8],
+        mut credential: Credential<T::AccountId, T::Hash, T::Moment>,
+        status: CredentialStatus,
+        by: Option<T::AccountId>,
+        reason: Option<ReasonCode>,
+    ) {
+        credential.status = status;
+        let change = StatusChange::new(
+            status,
+            by,
+            <system::Module<T>>::block_number(),
+            <timestamp::Module<T>>::now(),
+            reason,
+        );
+        <Credentials<T>>::insert(id, credential);
+        <StatusHistory<T>>::append(id, change);
+    }
+
+    /// Returns the status of a credential, or `None` if it was never registered.
+    pub fn credential_status(id: &[u8]) -> Option<CredentialStatus> {
+        Self::cred_by_id(id).map(|credential| credential.status())
+    }
+
+    /// Returns true if the credential exists, is active and has not expired.
+    /// Expiry takes effect without an explicit revocation transaction.
+    pub fn is_credential_valid(id: &[u8]) -> bool {
+        let now = <timestamp::Module<T>>::now();
+        Self::cred_by_id(id).map_or(false, |credential| credential.is_valid_at(&now))
+    }
+
+    /// Looks up a credential by id, or every credential anchored under a hash,
+    /// and reports issuer, registration time, status and a validity verdict.
+    /// Backs the `CredentialApi` runtime API.
+    pub fn verify_credential(
+        lookup: CredentialLookup<T::Hash>,
+    ) -> Vec<CredentialVerification<T::AccountId, T::Hash, T::Moment>> {
+        let credentials: Vec<_> = match lookup {
+            CredentialLookup::Id(id) => Self::cred_by_id(&id).into_iter().collect(),
+            CredentialLookup::Hash(hash) => Self::cred_by_hash(hash)
+                .iter()
+                .filter_map(|id| Self::cred_by_id(id))
+                .collect(),
+        };
+        let now = <timestamp::Module<T>>::now();
+        credentials
+            .into_iter()
+            .map(|credential| CredentialVerification {
+                valid: credential.is_valid_at(&now),
+                id: credential.id,
+                issuer: credential.owner,
+                signer: credential.signer,
+                claims_root: credential.claims_root,
+                schema_id: credential.schema_id,
+                registered: credential.registered,
+                status: credential.status,
+            })
+            .collect()
+    }
+
     pub fn validate_cred_id(id: &[u8]) -> Result<(), Error<T>> {
         ensure!(!id.is_empty(), Error::<T>::CredIdMissing);
         ensure!(id.len() <= CRED_ID_MAX_LENGTH, Error::<T>::CredIdTooLong);
@@ -183,13 +1198,209 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    pub fn validate_issuer(issuer: &T::AccountId, who: &T::AccountId) -> Result<(), Error<T>> {
+        ensure!(
+            T::IssuerCheck::is_issuer(issuer, who),
+            Error::<T>::CredNotIssuer
+        );
+        Ok(())
+    }
+
+    /// Checks `who` acts on behalf of the `issuer` organization and may manage its credentials.
+    pub fn validate_acting_issuer(issuer: &T::AccountId, who: &T::AccountId, org: &T::AccountId) -> Result<(), Error<T>> {
+        ensure!(issuer == org, Error::<T>::CredNotActingOrganization);
+        Self::validate_issuer(issuer, who)
+    }
+
+    pub fn validate_cred_schema(schema_id: &[u8]) -> Result<(), Error<T>> {
+        ensure!(
+            <pallet_schema::Module<T>>::schema_by_id(schema_id).is_some(),
+            Error::<T>::CredSchemaMissing
+        );
+        ensure!(
+            <pallet_schema::Module<T>>::is_schema_active(schema_id),
+            Error::<T>::CredSchemaInactive
+        );
+        Ok(())
+    }
+
+    /// Returns the message an issuer signs for a credential: the SCALE encoding of
+    /// `(id, hash, claims_root, schema_id, genesis_hash)`. The genesis hash binds it to this chain.
+    pub fn credential_signing_payload(
+        id: &[u8],
+        hash: &T::Hash,
+        claims_root: &Option<T::Hash>,
+        schema_id: &[u8],
+    ) -> Vec<u8> {
+        let genesis = <system::Module<T>>::block_hash(T::BlockNumber::zero());
+        (id, hash, claims_root, schema_id, genesis).encode()
+    }
+
+    /// Returns the message a holder signs to consent to a presentation: the SCALE
+    /// encoding of `(id, verifier, purpose, expires, genesis_hash)`. A consent can be
+    /// used once, before the `expires` block.
+    pub fn presentation_consent_payload(
+        id: &[u8],
+        verifier: &T::AccountId,
+        purpose: &[u8],
+        expires: &T::BlockNumber,
+    ) -> Vec<u8> {
+        let genesis = <system::Module<T>>::block_hash(T::BlockNumber::zero());
+        (id, verifier, purpose, expires, genesis).encode()
+    }
+
+    // Records the answer of the holder of a credential. The holder DID, or the
+    // account currently owning it, must sign.
+    fn respond_as_holder(who: &T::AccountId, id: &[u8], response: HolderResponse) -> Result<(), Error<T>> {
+        Self::validate_cred_id(id)?;
+
+        // Check the credential exists and is held by the caller (2 DB reads)
+        let credential = Self::cred_by_id(id).ok_or(Error::<T>::CredIdMissing)?;
+        let holder = credential.holder().ok_or(Error::<T>::CredNotHolder)?;
+        ensure!(
+            <did::Module<T>>::is_owner(holder, who).is_ok(),
+            Error::<T>::CredNotHolder
+        );
+        ensure!(
+            !<HolderResponses>::contains_key(id),
+            Error::<T>::CredHolderResponded
+        );
+
+        // Record the answer (1 DB write)
+        <HolderResponses>::insert(id, response);
+        Ok(())
+    }
+
+    /// Returns true if `proof` shows that `hash` is one of the credentials anchored by a batch.
+    /// The proof must hold exactly one node per level of the batch tree.
+    pub fn verify_batch_inclusion(id: &[u8], hash: T::Hash, proof: &[T::Hash]) -> bool {
+        Self::batch_by_id(id).map_or(false, |batch| {
+            proof.len() == merkle_proof_length(batch.count)
+                && merkle_root_from_proof::<T::Hashing>(merkle_leaf::<T::Hashing>(hash.as_ref()), proof)
+                    == batch.root
+        })
+    }
+
+    pub fn validate_batch_id(id: &[u8]) -> Result<(), Error<T>> {
+        ensure!(!id.is_empty(), Error::<T>::BatchIdMissing);
+        ensure!(id.len() <= BATCH_ID_MAX_LENGTH, Error::<T>::BatchIdTooLong);
+        Ok(())
+    }
+
+    /// Returns the packed status list bitstring and its hash, which verifiers use to
+    /// build a StatusList2021 credential.
+    pub fn encoded_status_list(id: &[u8]) -> Option<(Vec<u8>, T::Hash)> {
+        Self::status_list(id).map(|list| {
+            let hash = T::Hashing::hash(list.bits());
+            (list.bits, hash)
+        })
+    }
+
+    /// Returns a status list with its packed bitstring and hash. Backs the `CredentialApi`
+    /// runtime API.
+    pub fn status_list_snapshot(id: &[u8]) -> Option<StatusListSnapshot<T::AccountId, T::Hash>> {
+        Self::status_list(id).map(|list| StatusListSnapshot {
+            hash: T::Hashing::hash(list.bits()),
+            issuer: list.issuer,
+            purpose: list.purpose,
+            length: list.length,
+            bits: list.bits,
+        })
+    }
+
+    /// Returns the status bit at `index`, or `None` if the list or index doesn't exist.
+    pub fn status_list_entry(id: &[u8], index: u32) -> Option<bool> {
+        Self::status_list(id).and_then(|list| list.get(index))
+    }
+
+    // Sets the status of the entries in `start..end` of a list.
+    fn update_status_list_entries(
+        origin: T::Origin,
+        id: StatusListId,
+        start: u32,
+        end: u32,
+        status: bool,
+    ) -> dispatch::DispatchResultWithPostInfo {
+        let (who, org) = <T as Trait>::CreateRoleOrigin::ensure_origin(origin)?;
+
+        Self::validate_status_list_id(&id)?;
+
+        // Check the list exists and the caller may manage it (2 DB reads)
+        let mut list = Self::status_list(&id).ok_or(Error::<T>::StatusListIdMissing)?;
+        Self::validate_acting_issuer(list.issuer(), &who, &org)?;
+        ensure!(
+            start < end && end <= list.length(),
+            Error::<T>::StatusListIndexOutOfRange
+        );
+
+        // Rewrite the list (1 DB write)
+        list.set_range(start, end, status);
+        list.updated = <timestamp::Module<T>>::now();
+        let hash = T::Hashing::hash(list.bits());
+        let weight = status_list_weight::<T>(list.length(), end - start);
+        <StatusLists<T>>::insert(&id, list);
+
+        Self::deposit_event(RawEvent::StatusListUpdated(who, id, hash));
+
+        Ok(Some(weight).into())
+    }
+
+    pub fn validate_status_list_id(id: &[u8]) -> Result<(), Error<T>> {
+        ensure!(!id.is_empty(), Error::<T>::StatusListIdMissing);
+        ensure!(
+            id.len() <= STATUS_LIST_ID_MAX_LENGTH,
+            Error::<T>::StatusListIdTooLong
+        );
+        Ok(())
+    }
+
+    pub fn validate_cred_validity(
+        now: &T::Moment,
+        valid_from: &Option<T::Moment>,
+        valid_until: &Option<T::Moment>,
+    ) -> Result<(), Error<T>> {
+        if let Some(until) = valid_until {
+            ensure!(until > now, Error::<T>::CredAlreadyExpired);
+            if let Some(from) = valid_from {
+                ensure!(from < until, Error::<T>::CredInvalidValidityPeriod);
+            }
+        }
+        Ok(())
+    }
+
     pub fn validate_cred_props(props: &Option<Vec<CredProperty>>) -> Result<(), Error<T>> {
         if let Some(props) = props {
-            ensure!(props.len() <= CRED_MAX_PROPS, Error::<T>::CredTooManyProps,);
+            ensure!(props.len() <= T::MaxProps::get() as usize, Error::<T>::CredTooManyProps);
+            for (index, prop) in props.iter().enumerate() {
+                ensure!(
+                    !prop.key().is_empty() && prop.key().len() <= T::MaxPropKeyLength::get() as usize,
+                    Error::<T>::CredInvalidPropKey
+                );
+                ensure!(
+                    prop.value().size() <= T::MaxPropValueLength::get() as usize,
+                    Error::<T>::CredPropValueTooLong
+                );
+                ensure!(
+                    !props[..index].iter().any(|other| other.key() == prop.key()),
+                    Error::<T>::CredDuplicatePropKey
+                );
+            }
+        }
+        Ok(())
+    }
+
+    // Schemas declaring properties restrict the claim keys of their credentials.
+    pub fn validate_props_against_schema(
+        schema_id: &[u8],
+        props: &Option<Vec<CredProperty>>,
+    ) -> Result<(), Error<T>> {
+        let declared = <pallet_schema::Module<T>>::schema_by_id(schema_id)
+            .and_then(|schema| schema.props().cloned());
+        if let (Some(props), Some(declared)) = (props, declared) {
             for prop in props {
                 ensure!(
-                    prop.subject().len() <= CRED_SUBJ_MAX_LENGTH,
-                    Error::<T>::CredInvalidSubject
+                    declared.iter().any(|schema_prop| schema_prop.name() == prop.key()),
+                    Error::<T>::CredPropNotInSchema
                 );
             }
         }
@@ -207,8 +1418,15 @@ where
     id: CredId,
     owner: AccountId,
     hash: Hash,
+    claims_root: Option<Hash>,
+    schema_id: SchemaId,
+    holder: Option<AccountId>,
     props: Option<Vec<CredProperty>>,
     registered: Moment,
+    status: CredentialStatus,
+    valid_from: Option<Moment>,
+    valid_until: Option<Moment>,
+    signer: AccountId,
 }
 
 impl<AccountId, Hash, Moment> CredentialBuilder<AccountId, Hash, Moment>
@@ -231,6 +1449,21 @@ where
         self
     }
 
+    pub fn with_claims_root(mut self, claims_root: Option<Hash>) -> Self {
+        self.claims_root = claims_root;
+        self
+    }
+
+    pub fn with_schema(mut self, schema_id: SchemaId) -> Self {
+        self.schema_id = schema_id;
+        self
+    }
+
+    pub fn held_by(mut self, holder: Option<AccountId>) -> Self {
+        self.holder = holder;
+        self
+    }
+
     pub fn with_props(mut self, props: Option<Vec<CredProperty>>) -> Self {
         self.props = props;
         self
@@ -241,13 +1474,31 @@ where
         self
     }
 
+    pub fn valid_between(mut self, valid_from: Option<Moment>, valid_until: Option<Moment>) -> Self {
+        self.valid_from = valid_from;
+        self.valid_until = valid_until;
+        self
+    }
+
+    pub fn signed_by(mut self, signer: AccountId) -> Self {
+        self.signer = signer;
+        self
+    }
+
     pub fn build(self) -> Credential<AccountId, Hash, Moment> {
         Credential::<AccountId, Hash, Moment> {
             id: self.id,
             owner: self.owner,
             hash: self.hash,
+            claims_root: self.claims_root,
+            schema_id: self.schema_id,
+            holder: self.holder,
             props: self.props,
             registered: self.registered,
+            status: self.status,
+            valid_from: self.valid_from,
+            valid_until: self.valid_until,
+            signer: self.signer,
         }
     }
 }
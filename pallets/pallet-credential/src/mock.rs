@@ -1,6 +1,6 @@
 // Creating mock runtime here
 
-use crate::{Module, Trait};
+use crate::{DelegateIssuerCheck, Module, Trait};
 use core::marker::PhantomData;
 use frame_support::{
     impl_outer_event, impl_outer_origin, parameter_types, traits::EnsureOrigin, weights::Weight,
@@ -23,6 +23,8 @@ impl_outer_origin! {
 impl_outer_event! {
     pub enum TestEvent for Test {
         system<T>,
+        did<T>,
+        pallet_schema<T>,
         credential_registry<T>,
     }
 }
@@ -37,6 +39,10 @@ parameter_types! {
     pub const MaximumBlockWeight: Weight = 1024;
     pub const MaximumBlockLength: u32 = 2 * 1024;
     pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+    pub const IssuerDelegateType: &'static [u8] = b"OrgMember";
+    pub const MaxProps: u32 = 2;
+    pub const MaxPropKeyLength: u32 = 16;
+    pub const MaxPropValueLength: u32 = 48;
 }
 
 impl system::Trait for Test {
@@ -74,23 +80,43 @@ impl timestamp::Trait for Test {
     type WeightInfo = ();
 }
 
+impl did::Trait for Test {
+    type Event = TestEvent;
+    type Public = sr25519::Public;
+    type Signature = sr25519::Signature;
+}
+
+impl pallet_schema::Trait for Test {
+    type Event = TestEvent;
+    type CreateRoleOrigin = MockOrigin<Test>;
+}
+
 impl Trait for Test {
     type Event = TestEvent;
     type CreateRoleOrigin = MockOrigin<Test>;
+    type IssuerCheck = DelegateIssuerCheck<Test, IssuerDelegateType>;
+    type MaxProps = MaxProps;
+    type MaxPropKeyLength = MaxPropKeyLength;
+    type MaxPropValueLength = MaxPropValueLength;
 }
 
 pub type CredentialRegistry = Module<Test>;
+pub type SchemaRegistry = pallet_schema::Module<Test>;
+pub type DID = did::Module<Test>;
 pub type System = system::Module<Test>;
 pub type Timestamp = timestamp::Module<Test>;
 
 pub struct MockOrigin<T>(PhantomData<T>);
 
-impl<T: Trait> EnsureOrigin<T::Origin> for MockOrigin<T> {
-    type Success = T::AccountId;
-    fn try_origin(o: T::Origin) -> Result<Self::Success, T::Origin> {
+/// Organization every signed account acts for in the mock.
+pub const MOCK_ORGANIZATION: &str = "Dhiway Test";
+
+impl EnsureOrigin<Origin> for MockOrigin<Test> {
+    type Success = (sr25519::Public, sr25519::Public);
+    fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
         o.into().and_then(|o| match o {
-            RawOrigin::Signed(ref who) => Ok(who.clone()),
-            r => Err(T::Origin::from(r)),
+            RawOrigin::Signed(who) => Ok((who, account_key(MOCK_ORGANIZATION))),
+            r => Err(Origin::from(r)),
         })
     }
 }
@@ -109,6 +135,10 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     ext
 }
 
+pub fn account_pair(s: &str) -> sr25519::Pair {
+    sr25519::Pair::from_string(&format!("//{}", s), None).expect("static values are valid; qed")
+}
+
 pub fn account_key(s: &str) -> sr25519::Public {
     sr25519::Pair::from_string(&format!("//{}", s), None)
         .expect("static values are valid; qed")
@@ -2,31 +2,106 @@
 
 use super::*;
 use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok, dispatch};
-use sp_core::H256;
+use frame_support::{assert_noop, assert_ok, dispatch, traits::OnInitialize};
+use sp_core::{sr25519, Pair, H256};
+use sp_runtime::traits::BlakeTwo256;
+use pallet_schema::SchemaProperty;
 
 pub fn store_test_credential<T: Trait>(
     id: CredId,
     owner: T::AccountId,
     hash: T::Hash,
+    schema_id: SchemaId,
     registered: T::Moment,
 ) {
     Credentials::<T>::insert(
         id.clone(),
         Credential {
             id,
-            owner,
+            owner: owner.clone(),
             hash,
+            claims_root: None,
+            schema_id,
+            holder: None,
             registered,
             props: None,
+            status: CredentialStatus::Active,
+            valid_from: None,
+            valid_until: None,
+            signer: owner,
         },
     );
 }
 
+pub fn register_test_schema(owner: <Test as system::Trait>::AccountId) -> SchemaId {
+    let schema_id = TEST_SCHEMA_ID.as_bytes().to_owned();
+    assert_ok!(SchemaRegistry::register_schema(
+        Origin::signed(owner),
+        schema_id.clone(),
+        H256::from_low_u64_be(100),
+        None,
+    ));
+    schema_id
+}
+
+pub fn add_test_issuer(
+    owner: <Test as system::Trait>::AccountId,
+    issuer: <Test as system::Trait>::AccountId,
+) {
+    assert_ok!(DID::add_delegate(
+        Origin::signed(owner),
+        owner,
+        issuer,
+        IssuerDelegateType::get().to_vec(),
+        None,
+    ));
+}
+
+pub fn register_test_credential(
+    issuer: <Test as system::Trait>::AccountId,
+    owner: <Test as system::Trait>::AccountId,
+) -> CredId {
+    let id = TEST_CRED_ID.as_bytes().to_owned();
+    let schema_id = register_test_schema(owner);
+    add_test_issuer(owner, issuer);
+    assert_ok!(CredentialRegistry::register_credential(
+        Origin::signed(issuer),
+        id.clone(),
+        owner,
+        H256::from_low_u64_be(1),
+        None,
+        schema_id.clone(),
+        None,
+        None,
+        None,
+        None,
+        account_key(TEST_ORGANIZATION),
+        issuer_signature(&id, &H256::from_low_u64_be(1), &schema_id),
+    ));
+    id
+}
+
+pub fn issuer_signature(id: &[u8], hash: &H256, schema_id: &[u8]) -> sr25519::Signature {
+    let payload = CredentialRegistry::credential_signing_payload(id, hash, &None, schema_id);
+    account_pair(TEST_ORGANIZATION).sign(&payload)
+}
+
 const TEST_ORGANIZATION: &str = "Dhiway Test";
 const TEST_SENDER: &str = "Ashok";
 const TEST_CRED_ID: &str = "00012345600012";
 const TEST_CRED_SUBJ: &str = "Test Event Completion for Dhiway";
+const TEST_SCHEMA_ID: &str = "00098765400098";
+const TEST_STATUS_LIST_ID: &str = "SL-2021-0001";
+const TEST_BATCH_ID: &str = "BATCH-2020-0001";
+
+fn hash_leaf(value: H256) -> H256 {
+    BlakeTwo256::hash(&[&[0u8][..], value.as_bytes()].concat())
+}
+
+fn hash_pair(a: H256, b: H256) -> H256 {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    BlakeTwo256::hash(&[&[1u8][..], first.as_bytes(), second.as_bytes()].concat())
+}
 
 #[test]
 fn create_product_without_props() {
@@ -37,6 +112,7 @@ fn create_product_without_props() {
         let hash = H256::from_low_u64_be(1);
         let now = 42;
         Timestamp::set_timestamp(now);
+        let schema_id = register_test_schema(owner);
 
         let result = CredentialRegistry::register_credential(
             Origin::signed(sender),
@@ -44,6 +120,13 @@ fn create_product_without_props() {
             owner.clone(),
             hash.clone(),
             None,
+            schema_id.clone(),
+            None,
+            None,
+            None,
+            None,
+            account_key(TEST_ORGANIZATION),
+            issuer_signature(&id, &hash, &schema_id),
         );
 
         assert_ok!(result);
@@ -54,14 +137,22 @@ fn create_product_without_props() {
                 id: id.clone(),
                 owner: owner,
                 hash: hash,
+                claims_root: None,
+                schema_id: schema_id.clone(),
+                holder: None,
                 registered: now,
-                props: None
+                props: None,
+                status: CredentialStatus::Active,
+                valid_from: None,
+                valid_until: None,
+                signer: owner,
             })
         );
 
         assert_eq!(<CredsOfOrganization<Test>>::get(owner), vec![id.clone()]);
         assert_eq!(CredentialRegistry::issuer_of_cred(&id), Some(owner));
         assert_eq!(<CredByHash<Test>>::get(hash), vec![id.clone()]);
+        assert_eq!(CredentialRegistry::credentials_of_schema(&schema_id), vec![id.clone()]);
 
         // Event is raised
         assert!(System::events().iter().any(|er| er.event
@@ -82,15 +173,24 @@ fn create_cred_with_valid_props() {
         let hash = H256::from_low_u64_be(1);
         let now = 42;
         Timestamp::set_timestamp(now);
+        let schema_id = register_test_schema(owner);
 
         let result = CredentialRegistry::register_credential(
             Origin::signed(sender),
             id.clone(),
             owner.clone(),
             hash.clone(),
+            None,
+            schema_id.clone(),
+            None,
             Some(vec![CredProperty::new(
-                &TEST_CRED_SUBJ.as_bytes().to_owned(),
+                b"subject",
+                ClaimValue::Text(TEST_CRED_SUBJ.as_bytes().to_owned()),
             )]),
+            None,
+            None,
+            account_key(TEST_ORGANIZATION),
+            issuer_signature(&id, &hash, &schema_id),
         );
 
         assert_ok!(result);
@@ -101,16 +201,25 @@ fn create_cred_with_valid_props() {
                 id: id.clone(),
                 owner: owner,
                 hash: hash.clone(),
+                claims_root: None,
+                schema_id: schema_id.clone(),
+                holder: None,
                 registered: now,
                 props: Some(vec![CredProperty::new(
-                    &TEST_CRED_SUBJ.as_bytes().to_owned()
+                    b"subject",
+                    ClaimValue::Text(TEST_CRED_SUBJ.as_bytes().to_owned())
                 )]),
+                status: CredentialStatus::Active,
+                valid_from: None,
+                valid_until: None,
+                signer: owner,
             })
         );
 
         assert_eq!(<CredsOfOrganization<Test>>::get(owner), vec![id.clone()]);
         assert_eq!(CredentialRegistry::issuer_of_cred(&id), Some(owner));
         assert_eq!(<CredByHash<Test>>::get(hash), vec![id.clone()]);
+        assert_eq!(CredentialRegistry::credentials_of_schema(&schema_id), vec![id.clone()]);
 
         // Event is raised
         assert!(System::events().iter().any(|er| er.event
@@ -132,7 +241,14 @@ fn create_cred_with_invalid_sender() {
                 vec!(),
                 account_key(TEST_ORGANIZATION),
                 hash.clone(),
-                None
+                None,
+                TEST_SCHEMA_ID.as_bytes().to_owned(),
+                None,
+                None,
+                None,
+                None,
+                account_key(TEST_ORGANIZATION),
+                issuer_signature(&[], &hash, TEST_SCHEMA_ID.as_bytes())
             ),
             dispatch::DispatchError::BadOrigin
         );
@@ -149,7 +265,14 @@ fn create_cred_with_missing_id() {
                 vec!(),
                 account_key(TEST_ORGANIZATION),
                 hash.clone(),
-                None
+                None,
+                TEST_SCHEMA_ID.as_bytes().to_owned(),
+                None,
+                None,
+                None,
+                None,
+                account_key(TEST_ORGANIZATION),
+                issuer_signature(&[], &hash, TEST_SCHEMA_ID.as_bytes())
             ),
             Error::<Test>::CredIdMissing
         );
@@ -166,7 +289,18 @@ fn create_cred_with_long_id() {
                 b"ajasljdfalsjfasdfjasfasdlfjasdflkajdsflkajdsfalksjdfalksdjfadfasdf".to_vec(),
                 account_key(TEST_ORGANIZATION),
                 hash.clone(),
-                None
+                None,
+                TEST_SCHEMA_ID.as_bytes().to_owned(),
+                None,
+                None,
+                None,
+                None,
+                account_key(TEST_ORGANIZATION),
+                issuer_signature(
+                    b"ajasljdfalsjfasdfjasfasdlfjasdflkajdsflkajdsfalksjdfalksdjfadfasdf",
+                    &hash,
+                    TEST_SCHEMA_ID.as_bytes()
+                )
             ),
             Error::<Test>::CredIdTooLong
         );
@@ -184,16 +318,24 @@ fn create_cred_with_existing_id() {
             existing_cred.clone(),
             account_key(TEST_ORGANIZATION),
             hash.clone(),
+            TEST_SCHEMA_ID.as_bytes().to_owned(),
             now,
         );
 
         assert_noop!(
             CredentialRegistry::register_credential(
                 Origin::signed(account_key(TEST_SENDER)),
-                existing_cred,
+                existing_cred.clone(),
                 account_key(TEST_ORGANIZATION),
                 hash.clone(),
-                None
+                None,
+                TEST_SCHEMA_ID.as_bytes().to_owned(),
+                None,
+                None,
+                None,
+                None,
+                account_key(TEST_ORGANIZATION),
+                issuer_signature(&existing_cred, &hash, TEST_SCHEMA_ID.as_bytes())
             ),
             Error::<Test>::CredIdExists
         );
@@ -210,10 +352,18 @@ fn create_cred_with_too_many_props() {
                 TEST_CRED_ID.as_bytes().to_owned(),
                 account_key(TEST_ORGANIZATION),
                 hash.clone(),
+                None,
+                TEST_SCHEMA_ID.as_bytes().to_owned(),
+                None,
                 Some(vec![
-                    CredProperty::new(b"Subject to Ashok Kumar"),
-                    CredProperty::new(b"Subject to John Doe"),
-                ])
+                    CredProperty::new(b"subject", ClaimValue::Text(b"Ashok Kumar".to_vec())),
+                    CredProperty::new(b"grade", ClaimValue::Integer(9)),
+                    CredProperty::new(b"passed", ClaimValue::Boolean(true)),
+                ]),
+                None,
+                None,
+                account_key(TEST_ORGANIZATION),
+                issuer_signature(TEST_CRED_ID.as_bytes(), &hash, TEST_SCHEMA_ID.as_bytes())
             ),
             Error::<Test>::CredTooManyProps
         );
@@ -221,7 +371,7 @@ fn create_cred_with_too_many_props() {
 }
 
 #[test]
-fn create_cred_with_invalid_subject() {
+fn create_cred_with_long_prop_value() {
     new_test_ext().execute_with(|| {
         let hash = H256::from_low_u64_be(1);
         assert_noop!(
@@ -230,11 +380,1844 @@ fn create_cred_with_invalid_subject() {
                 TEST_CRED_ID.as_bytes().to_owned(),
                 account_key(TEST_ORGANIZATION),
                 hash.clone(),
+                None,
+                TEST_SCHEMA_ID.as_bytes().to_owned(),
+                None,
                 Some(vec![CredProperty::new(
-                    b"This is a long event name where I can't get it for a particular person"
-                )])
+                    b"subject",
+                    ClaimValue::Text(
+                        b"This is a long event name where I can't get it for a particular person".to_vec()
+                    )
+                )]),
+                None,
+                None,
+                account_key(TEST_ORGANIZATION),
+                issuer_signature(TEST_CRED_ID.as_bytes(), &hash, TEST_SCHEMA_ID.as_bytes())
+            ),
+            Error::<Test>::CredPropValueTooLong
+        );
+    })
+}
+
+#[test]
+fn create_cred_with_unknown_schema() {
+    new_test_ext().execute_with(|| {
+        let hash = H256::from_low_u64_be(1);
+        assert_noop!(
+            CredentialRegistry::register_credential(
+                Origin::signed(account_key(TEST_SENDER)),
+                TEST_CRED_ID.as_bytes().to_owned(),
+                account_key(TEST_ORGANIZATION),
+                hash.clone(),
+                None,
+                TEST_SCHEMA_ID.as_bytes().to_owned(),
+                None,
+                None,
+                None,
+                None,
+                account_key(TEST_ORGANIZATION),
+                issuer_signature(TEST_CRED_ID.as_bytes(), &hash, TEST_SCHEMA_ID.as_bytes())
+            ),
+            Error::<Test>::CredSchemaMissing
+        );
+    })
+}
+
+#[test]
+fn create_cred_with_inactive_schema() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let hash = H256::from_low_u64_be(1);
+        let schema_id = register_test_schema(owner);
+        assert_ok!(SchemaRegistry::deactivate_schema(
+            Origin::signed(owner),
+            schema_id.clone()
+        ));
+
+        assert_noop!(
+            CredentialRegistry::register_credential(
+                Origin::signed(account_key(TEST_SENDER)),
+                TEST_CRED_ID.as_bytes().to_owned(),
+                owner,
+                hash.clone(),
+                None,
+                schema_id.clone(),
+                None,
+                None,
+                None,
+                None,
+                account_key(TEST_ORGANIZATION),
+                issuer_signature(TEST_CRED_ID.as_bytes(), &hash, &schema_id)
+            ),
+            Error::<Test>::CredSchemaInactive
+        );
+    })
+}
+
+#[test]
+fn revoke_cred_keeps_record() {
+    new_test_ext().execute_with(|| {
+        let sender = account_key(TEST_SENDER);
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let owner = account_key(TEST_ORGANIZATION);
+        let hash = H256::from_low_u64_be(1);
+        let schema_id = register_test_schema(owner);
+        add_test_issuer(owner, sender);
+        Timestamp::set_timestamp(42);
+
+        assert_ok!(CredentialRegistry::register_credential(
+            Origin::signed(sender),
+            id.clone(),
+            owner.clone(),
+            hash.clone(),
+            None,
+            schema_id.clone(),
+            None,
+            None,
+            None,
+            None,
+            account_key(TEST_ORGANIZATION),
+            issuer_signature(&id, &hash, &schema_id),
+        ));
+        assert_eq!(
+            CredentialRegistry::credential_status(&id),
+            Some(CredentialStatus::Active)
+        );
+
+        System::set_block_number(5);
+        Timestamp::set_timestamp(84);
+        assert_ok!(CredentialRegistry::revoke_credential(
+            Origin::signed(sender),
+            id.clone(),
+            owner.clone(),
+            hash.clone(),
+            Some(7),
+        ));
+
+        // Credential and its indexes are retained with a revoked status
+        assert_eq!(
+            CredentialRegistry::credential_status(&id),
+            Some(CredentialStatus::Revoked)
+        );
+        assert_eq!(<CredsOfOrganization<Test>>::get(owner), vec![id.clone()]);
+        assert_eq!(CredentialRegistry::issuer_of_cred(&id), Some(owner));
+        assert_eq!(<CredByHash<Test>>::get(hash), vec![id.clone()]);
+        assert_eq!(
+            CredentialRegistry::revocation_of(&id),
+            Some(Revocation::new(sender, 5, 84, Some(7)))
+        );
+
+        // Event is raised
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::credential_registry(RawEvent::CredentialRevoked(
+                sender,
+                id.clone(),
+                hash.clone(),
+            ))));
+
+        assert_noop!(
+            CredentialRegistry::revoke_credential(
+                Origin::signed(sender),
+                id.clone(),
+                owner.clone(),
+                hash.clone(),
+                None,
+            ),
+            Error::<Test>::CredAlreadyRevoked
+        );
+    })
+}
+
+#[test]
+fn revoke_cred_with_unknown_id() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            CredentialRegistry::revoke_credential(
+                Origin::signed(account_key(TEST_SENDER)),
+                TEST_CRED_ID.as_bytes().to_owned(),
+                account_key(TEST_ORGANIZATION),
+                H256::from_low_u64_be(1),
+                None,
+            ),
+            Error::<Test>::CredIdMissing
+        );
+        assert_eq!(
+            CredentialRegistry::credential_status(TEST_CRED_ID.as_bytes()),
+            None
+        );
+    })
+}
+
+#[test]
+fn revoke_cred_with_mismatched_hash() {
+    new_test_ext().execute_with(|| {
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let owner = account_key(TEST_ORGANIZATION);
+
+        store_test_credential::<Test>(
+            id.clone(),
+            owner.clone(),
+            H256::from_low_u64_be(1),
+            TEST_SCHEMA_ID.as_bytes().to_owned(),
+            42,
+        );
+
+        assert_noop!(
+            CredentialRegistry::revoke_credential(
+                Origin::signed(account_key(TEST_SENDER)),
+                id,
+                owner,
+                H256::from_low_u64_be(2),
+                None,
+            ),
+            Error::<Test>::CredDetailsMismatch
+        );
+    })
+}
+
+#[test]
+fn revoke_cred_by_non_issuer() {
+    new_test_ext().execute_with(|| {
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let owner = account_key(TEST_ORGANIZATION);
+        let hash = H256::from_low_u64_be(1);
+
+        store_test_credential::<Test>(
+            id.clone(),
+            owner.clone(),
+            hash.clone(),
+            TEST_SCHEMA_ID.as_bytes().to_owned(),
+            42,
+        );
+
+        assert_noop!(
+            CredentialRegistry::revoke_credential(
+                Origin::signed(account_key("Mallory")),
+                id,
+                owner,
+                hash,
+                None,
             ),
-            Error::<Test>::CredInvalidSubject
+            Error::<Test>::CredNotIssuer
+        );
+    })
+}
+
+#[test]
+fn revoke_cred_by_issuing_organization() {
+    new_test_ext().execute_with(|| {
+        let sender = account_key(TEST_SENDER);
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let owner = account_key(TEST_ORGANIZATION);
+        let hash = H256::from_low_u64_be(1);
+        let schema_id = register_test_schema(owner);
+
+        assert_ok!(CredentialRegistry::register_credential(
+            Origin::signed(sender),
+            id.clone(),
+            owner.clone(),
+            hash.clone(),
+            None,
+            schema_id.clone(),
+            None,
+            None,
+            None,
+            None,
+            account_key(TEST_ORGANIZATION),
+            issuer_signature(&id, &hash, &schema_id),
+        ));
+
+        // The organization identity itself is always a valid issuer
+        assert_ok!(CredentialRegistry::revoke_credential(
+            Origin::signed(owner),
+            id.clone(),
+            owner.clone(),
+            hash.clone(),
+            None,
+        ));
+        assert_eq!(
+            CredentialRegistry::credential_status(&id),
+            Some(CredentialStatus::Revoked)
+        );
+    })
+}
+
+#[test]
+fn revoke_cred_leaves_other_org_credentials() {
+    new_test_ext().execute_with(|| {
+        let sender = account_key(TEST_SENDER);
+        let owner = account_key(TEST_ORGANIZATION);
+        let hash = H256::from_low_u64_be(1);
+        let other_hash = H256::from_low_u64_be(2);
+        let schema_id = register_test_schema(owner);
+        add_test_issuer(owner, sender);
+
+        let first = b"00012345600001".to_vec();
+        let second = b"00012345600002".to_vec();
+        let third = b"00012345600003".to_vec();
+
+        // Two credentials share a hash, a third has its own
+        for (id, hash) in vec![(&first, hash), (&second, hash), (&third, other_hash)] {
+            assert_ok!(CredentialRegistry::register_credential(
+                Origin::signed(sender),
+                id.clone(),
+                owner.clone(),
+                hash,
+                None,
+                schema_id.clone(),
+                None,
+                None,
+                None,
+                None,
+                account_key(TEST_ORGANIZATION),
+                issuer_signature(&id, &hash, &schema_id),
+            ));
+        }
+
+        assert_ok!(CredentialRegistry::revoke_credential(
+            Origin::signed(sender),
+            first.clone(),
+            owner.clone(),
+            hash.clone(),
+            None,
+        ));
+
+        assert_eq!(
+            <CredsOfOrganization<Test>>::get(owner),
+            vec![first.clone(), second.clone(), third.clone()]
+        );
+        assert_eq!(<CredByHash<Test>>::get(hash), vec![first.clone(), second.clone()]);
+        assert_eq!(<CredByHash<Test>>::get(other_hash), vec![third.clone()]);
+        assert_eq!(CredentialRegistry::issuer_of_cred(&second), Some(owner));
+        assert_eq!(
+            CredentialRegistry::credential_status(&first),
+            Some(CredentialStatus::Revoked)
+        );
+        assert_eq!(
+            CredentialRegistry::credential_status(&second),
+            Some(CredentialStatus::Active)
+        );
+        assert_eq!(
+            CredentialRegistry::credential_status(&third),
+            Some(CredentialStatus::Active)
+        );
+    })
+}
+
+#[test]
+fn suspend_and_reinstate_cred() {
+    new_test_ext().execute_with(|| {
+        let sender = account_key(TEST_SENDER);
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = register_test_credential(sender, owner);
+        Timestamp::set_timestamp(42);
+
+        assert_ok!(CredentialRegistry::suspend_credential(
+            Origin::signed(sender),
+            id.clone(),
+            Some(3),
+            None,
+        ));
+        assert_eq!(
+            CredentialRegistry::credential_status(&id),
+            Some(CredentialStatus::Suspended)
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::credential_registry(RawEvent::CredentialSuspended(
+                sender,
+                id.clone(),
+                None,
+            ))));
+
+        // A suspended credential cannot be suspended again
+        assert_noop!(
+            CredentialRegistry::suspend_credential(Origin::signed(sender), id.clone(), None, None),
+            Error::<Test>::CredNotActive
+        );
+
+        System::set_block_number(2);
+        assert_ok!(CredentialRegistry::reinstate_credential(
+            Origin::signed(sender),
+            id.clone(),
+        ));
+        assert_eq!(
+            CredentialRegistry::credential_status(&id),
+            Some(CredentialStatus::Active)
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::credential_registry(RawEvent::CredentialReinstated(
+                sender,
+                id.clone(),
+            ))));
+
+        assert_eq!(
+            CredentialRegistry::status_history(&id),
+            vec![
+                StatusChange::new(CredentialStatus::Suspended, Some(sender), 1, 42, Some(3)),
+                StatusChange::new(CredentialStatus::Active, Some(sender), 2, 42, None),
+            ]
+        );
+
+        assert_noop!(
+            CredentialRegistry::reinstate_credential(Origin::signed(sender), id),
+            Error::<Test>::CredNotSuspended
         );
     })
 }
+
+#[test]
+fn suspend_cred_by_non_issuer() {
+    new_test_ext().execute_with(|| {
+        let id = register_test_credential(account_key(TEST_SENDER), account_key(TEST_ORGANIZATION));
+
+        assert_noop!(
+            CredentialRegistry::suspend_credential(
+                Origin::signed(account_key("Mallory")),
+                id,
+                None,
+                None,
+            ),
+            Error::<Test>::CredNotIssuer
+        );
+    })
+}
+
+#[test]
+fn suspend_cred_with_past_reinstate_block() {
+    new_test_ext().execute_with(|| {
+        let sender = account_key(TEST_SENDER);
+        let id = register_test_credential(sender, account_key(TEST_ORGANIZATION));
+
+        assert_noop!(
+            CredentialRegistry::suspend_credential(Origin::signed(sender), id, None, Some(1)),
+            Error::<Test>::CredInvalidReinstateBlock
+        );
+    })
+}
+
+#[test]
+fn suspend_cred_with_full_reinstate_block() {
+    new_test_ext().execute_with(|| {
+        let sender = account_key(TEST_SENDER);
+        let id = register_test_credential(sender, account_key(TEST_ORGANIZATION));
+        ReinstatementsDue::<Test>::insert(10, vec![b"other".to_vec(); MAX_REINSTATEMENTS_PER_BLOCK]);
+
+        assert_noop!(
+            CredentialRegistry::suspend_credential(Origin::signed(sender), id.clone(), None, Some(10)),
+            Error::<Test>::CredReinstateBlockFull
+        );
+        assert_ok!(CredentialRegistry::suspend_credential(Origin::signed(sender), id.clone(), None, Some(11)));
+        assert_eq!(CredentialRegistry::reinstatements_due(11), vec![id]);
+    })
+}
+
+#[test]
+fn suspended_cred_is_reinstated_automatically() {
+    new_test_ext().execute_with(|| {
+        let sender = account_key(TEST_SENDER);
+        let id = register_test_credential(sender, account_key(TEST_ORGANIZATION));
+
+        assert_ok!(CredentialRegistry::suspend_credential(
+            Origin::signed(sender),
+            id.clone(),
+            None,
+            Some(10),
+        ));
+        assert_eq!(CredentialRegistry::reinstate_at(&id), Some(10));
+
+        System::set_block_number(9);
+        CredentialRegistry::on_initialize(9);
+        assert_eq!(
+            CredentialRegistry::credential_status(&id),
+            Some(CredentialStatus::Suspended)
+        );
+
+        System::set_block_number(10);
+        CredentialRegistry::on_initialize(10);
+        assert_eq!(
+            CredentialRegistry::credential_status(&id),
+            Some(CredentialStatus::Active)
+        );
+        assert_eq!(CredentialRegistry::reinstate_at(&id), None);
+        assert_eq!(
+            CredentialRegistry::status_history(&id).last(),
+            Some(&StatusChange::new(CredentialStatus::Active, None, 10, 0, None))
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::credential_registry(RawEvent::CredentialAutoReinstated(id.clone()))));
+    })
+}
+
+#[test]
+fn revoked_cred_is_not_reinstated_automatically() {
+    new_test_ext().execute_with(|| {
+        let sender = account_key(TEST_SENDER);
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = register_test_credential(sender, owner);
+
+        assert_ok!(CredentialRegistry::suspend_credential(
+            Origin::signed(sender),
+            id.clone(),
+            None,
+            Some(10),
+        ));
+        assert_ok!(CredentialRegistry::revoke_credential(
+            Origin::signed(sender),
+            id.clone(),
+            owner,
+            H256::from_low_u64_be(1),
+            None,
+        ));
+
+        System::set_block_number(10);
+        CredentialRegistry::on_initialize(10);
+        assert_eq!(
+            CredentialRegistry::credential_status(&id),
+            Some(CredentialStatus::Revoked)
+        );
+    })
+}
+
+#[test]
+fn create_cred_with_validity_window() {
+    new_test_ext().execute_with(|| {
+        let sender = account_key(TEST_SENDER);
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let schema_id = register_test_schema(owner);
+        Timestamp::set_timestamp(100);
+
+        assert_ok!(CredentialRegistry::register_credential(
+            Origin::signed(sender),
+            id.clone(),
+            owner,
+            H256::from_low_u64_be(1),
+            None,
+            schema_id.clone(),
+            None,
+            None,
+            Some(150),
+            Some(200),
+            account_key(TEST_ORGANIZATION),
+            issuer_signature(&id, &H256::from_low_u64_be(1), &schema_id),
+        ));
+
+        // Not yet valid
+        assert!(!CredentialRegistry::is_credential_valid(&id));
+
+        Timestamp::set_timestamp(150);
+        assert!(CredentialRegistry::is_credential_valid(&id));
+
+        // Expires without a revocation transaction
+        Timestamp::set_timestamp(200);
+        assert!(!CredentialRegistry::is_credential_valid(&id));
+        assert_eq!(
+            CredentialRegistry::credential_status(&id),
+            Some(CredentialStatus::Active)
+        );
+    })
+}
+
+#[test]
+fn suspended_cred_is_not_valid() {
+    new_test_ext().execute_with(|| {
+        let sender = account_key(TEST_SENDER);
+        let id = register_test_credential(sender, account_key(TEST_ORGANIZATION));
+        assert!(CredentialRegistry::is_credential_valid(&id));
+
+        assert_ok!(CredentialRegistry::suspend_credential(
+            Origin::signed(sender),
+            id.clone(),
+            None,
+            None,
+        ));
+        assert!(!CredentialRegistry::is_credential_valid(&id));
+        assert!(!CredentialRegistry::is_credential_valid(b"unknown"));
+    })
+}
+
+#[test]
+fn create_cred_already_expired() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let schema_id = register_test_schema(owner);
+        Timestamp::set_timestamp(100);
+
+        assert_noop!(
+            CredentialRegistry::register_credential(
+                Origin::signed(account_key(TEST_SENDER)),
+                TEST_CRED_ID.as_bytes().to_owned(),
+                owner,
+                H256::from_low_u64_be(1),
+                None,
+                schema_id.clone(),
+                None,
+                None,
+                None,
+                Some(100),
+                account_key(TEST_ORGANIZATION),
+                issuer_signature(TEST_CRED_ID.as_bytes(), &H256::from_low_u64_be(1), &schema_id),
+            ),
+            Error::<Test>::CredAlreadyExpired
+        );
+    })
+}
+
+#[test]
+fn create_cred_with_inverted_validity_window() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let schema_id = register_test_schema(owner);
+        Timestamp::set_timestamp(100);
+
+        assert_noop!(
+            CredentialRegistry::register_credential(
+                Origin::signed(account_key(TEST_SENDER)),
+                TEST_CRED_ID.as_bytes().to_owned(),
+                owner,
+                H256::from_low_u64_be(1),
+                None,
+                schema_id.clone(),
+                None,
+                None,
+                Some(300),
+                Some(200),
+                account_key(TEST_ORGANIZATION),
+                issuer_signature(TEST_CRED_ID.as_bytes(), &H256::from_low_u64_be(1), &schema_id),
+            ),
+            Error::<Test>::CredInvalidValidityPeriod
+        );
+    })
+}
+
+#[test]
+fn create_and_update_status_list() {
+    new_test_ext().execute_with(|| {
+        let sender = account_key(TEST_SENDER);
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = TEST_STATUS_LIST_ID.as_bytes().to_owned();
+        add_test_issuer(owner, sender);
+
+        assert_ok!(CredentialRegistry::create_status_list(
+            Origin::signed(sender),
+            id.clone(),
+            owner,
+            StatusPurpose::Revocation,
+            20,
+        ));
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::credential_registry(RawEvent::StatusListCreated(
+                sender,
+                id.clone(),
+                20,
+            ))));
+        assert_eq!(
+            CredentialRegistry::encoded_status_list(&id).map(|(bits, _)| bits),
+            Some(vec![0, 0, 0])
+        );
+
+        // Flip a single entry, most significant bit first
+        assert_ok!(CredentialRegistry::update_status_list(
+            Origin::signed(sender),
+            id.clone(),
+            3,
+            true,
+        ));
+        assert_eq!(CredentialRegistry::status_list_entry(&id, 3), Some(true));
+        assert_eq!(CredentialRegistry::status_list_entry(&id, 4), Some(false));
+
+        // Flip a range spanning two bytes
+        assert_ok!(CredentialRegistry::update_status_list_range(
+            Origin::signed(sender),
+            id.clone(),
+            6,
+            20,
+            true,
+        ));
+        assert_ok!(CredentialRegistry::update_status_list(
+            Origin::signed(sender),
+            id.clone(),
+            19,
+            false,
+        ));
+
+        let expected = vec![0b0001_0011, 0b1111_1111, 0b1110_0000];
+        assert_eq!(
+            CredentialRegistry::encoded_status_list(&id),
+            Some((expected.clone(), BlakeTwo256::hash(&expected)))
+        );
+        assert_eq!(CredentialRegistry::status_list_entry(&id, 20), None);
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::credential_registry(RawEvent::StatusListUpdated(
+                sender,
+                id.clone(),
+                BlakeTwo256::hash(&expected),
+            ))));
+    })
+}
+
+#[test]
+fn signed_credentials_are_weighed_by_their_signature_check() {
+    use frame_support::weights::GetDispatchInfo;
+
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let schema_id = TEST_SCHEMA_ID.as_bytes().to_owned();
+        let hash = H256::from_low_u64_be(1);
+        let signature = issuer_signature(&id, &hash, &schema_id);
+
+        let register = Call::<Test>::register_credential(id.clone(), owner, hash, None, schema_id,
+            None, None, None, None, owner, signature.clone());
+        assert_eq!(register.get_dispatch_info().weight, signed_weight::<Test>(6, 6));
+        assert!(register.get_dispatch_info().weight >= SIGNATURE_VERIFY_WEIGHT);
+
+        let reissue = Call::<Test>::reissue_credential(id.clone(), id, hash, None, None, None,
+            None, None, owner, signature, None);
+        assert!(reissue.get_dispatch_info().weight >= SIGNATURE_VERIFY_WEIGHT);
+    });
+}
+
+#[test]
+fn status_list_weight_scales_with_size() {
+    use frame_support::weights::GetDispatchInfo;
+
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = TEST_STATUS_LIST_ID.as_bytes().to_owned();
+        let create = |length| {
+            Call::<Test>::create_status_list(id.clone(), owner, StatusPurpose::Revocation, length)
+                .get_dispatch_info()
+                .weight
+        };
+        assert!(create(STATUS_LIST_MAX_LENGTH) > create(16));
+
+        let range = |start, end| {
+            Call::<Test>::update_status_list_range(id.clone(), start, end, true)
+                .get_dispatch_info()
+                .weight
+        };
+        assert!(range(0, 1_000) > range(0, 10));
+
+        // Updates are refunded down to the size of the list
+        assert_ok!(CredentialRegistry::create_status_list(
+            Origin::signed(owner),
+            id.clone(),
+            owner,
+            StatusPurpose::Revocation,
+            16,
+        ));
+        let post_info = CredentialRegistry::update_status_list_range(Origin::signed(owner), id.clone(), 2, 10, true)
+            .unwrap();
+        assert_eq!(post_info.actual_weight, Some(status_list_weight::<Test>(16, 8)));
+        assert!(post_info.actual_weight < Some(range(2, 10)));
+    })
+}
+
+#[test]
+fn status_list_snapshot_for_verifiers() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = TEST_STATUS_LIST_ID.as_bytes().to_owned();
+        assert_eq!(CredentialRegistry::status_list_snapshot(&id), None);
+
+        assert_ok!(CredentialRegistry::create_status_list(
+            Origin::signed(owner),
+            id.clone(),
+            owner,
+            StatusPurpose::Suspension,
+            12,
+        ));
+        assert_ok!(CredentialRegistry::update_status_list(Origin::signed(owner), id.clone(), 0, true));
+        assert_eq!(
+            CredentialRegistry::status_list_snapshot(&id),
+            Some(StatusListSnapshot {
+                issuer: owner,
+                purpose: StatusPurpose::Suspension,
+                length: 12,
+                bits: vec![0b1000_0000, 0],
+                hash: BlakeTwo256::hash(&[0b1000_0000, 0]),
+            })
+        );
+    })
+}
+
+#[test]
+fn update_status_list_out_of_range() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = TEST_STATUS_LIST_ID.as_bytes().to_owned();
+
+        assert_ok!(CredentialRegistry::create_status_list(
+            Origin::signed(owner),
+            id.clone(),
+            owner,
+            StatusPurpose::Suspension,
+            16,
+        ));
+
+        assert_noop!(
+            CredentialRegistry::update_status_list(Origin::signed(owner), id.clone(), 16, true),
+            Error::<Test>::StatusListIndexOutOfRange
+        );
+        assert_noop!(
+            CredentialRegistry::update_status_list_range(Origin::signed(owner), id.clone(), 8, 8, true),
+            Error::<Test>::StatusListIndexOutOfRange
+        );
+        assert_noop!(
+            CredentialRegistry::update_status_list(
+                Origin::signed(owner),
+                b"SL-unknown".to_vec(),
+                0,
+                true
+            ),
+            Error::<Test>::StatusListIdMissing
+        );
+    })
+}
+
+#[test]
+fn status_list_managed_by_issuer_only() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let mallory = account_key("Mallory");
+        let id = TEST_STATUS_LIST_ID.as_bytes().to_owned();
+
+        assert_noop!(
+            CredentialRegistry::create_status_list(
+                Origin::signed(mallory),
+                id.clone(),
+                owner,
+                StatusPurpose::Revocation,
+                16,
+            ),
+            Error::<Test>::CredNotIssuer
+        );
+
+        assert_ok!(CredentialRegistry::create_status_list(
+            Origin::signed(owner),
+            id.clone(),
+            owner,
+            StatusPurpose::Revocation,
+            16,
+        ));
+
+        assert_noop!(
+            CredentialRegistry::update_status_list(Origin::signed(mallory), id.clone(), 0, true),
+            Error::<Test>::CredNotIssuer
+        );
+        assert_noop!(
+            CredentialRegistry::create_status_list(
+                Origin::signed(owner),
+                id,
+                owner,
+                StatusPurpose::Revocation,
+                16,
+            ),
+            Error::<Test>::StatusListIdExists
+        );
+    })
+}
+
+#[test]
+fn create_status_list_with_invalid_length() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        for length in vec![0, STATUS_LIST_MAX_LENGTH + 1] {
+            assert_noop!(
+                CredentialRegistry::create_status_list(
+                    Origin::signed(owner),
+                    TEST_STATUS_LIST_ID.as_bytes().to_owned(),
+                    owner,
+                    StatusPurpose::Revocation,
+                    length,
+                ),
+                Error::<Test>::StatusListInvalidLength
+            );
+        }
+    })
+}
+
+#[test]
+fn register_batch_and_verify_inclusion() {
+    new_test_ext().execute_with(|| {
+        let sender = account_key(TEST_SENDER);
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = TEST_BATCH_ID.as_bytes().to_owned();
+        let schema_id = register_test_schema(owner);
+        add_test_issuer(owner, sender);
+        Timestamp::set_timestamp(42);
+
+        let leaves: Vec<H256> = (1..=4).map(H256::from_low_u64_be).collect();
+        let left = hash_pair(hash_leaf(leaves[0]), hash_leaf(leaves[1]));
+        let right = hash_pair(hash_leaf(leaves[2]), hash_leaf(leaves[3]));
+        let root = hash_pair(left, right);
+
+        assert_ok!(CredentialRegistry::register_credential_batch(
+            Origin::signed(sender),
+            id.clone(),
+            owner,
+            root,
+            schema_id.clone(),
+            4,
+        ));
+
+        assert_eq!(
+            CredentialRegistry::batch_by_id(&id),
+            Some(CredentialBatch::new(owner, schema_id, root, 4, 42))
+        );
+        assert_eq!(CredentialRegistry::batches_of_org(owner), vec![id.clone()]);
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::credential_registry(RawEvent::BatchRegistered(
+                sender,
+                id.clone(),
+                root,
+                4,
+            ))));
+
+        // Every leaf is provable against the anchored root
+        assert!(CredentialRegistry::verify_batch_inclusion(&id, leaves[0], &[hash_leaf(leaves[1]), right]));
+        assert!(CredentialRegistry::verify_batch_inclusion(&id, leaves[2], &[hash_leaf(leaves[3]), left]));
+        assert!(CredentialRegistry::verify_batch_inclusion(&id, leaves[3], &[hash_leaf(leaves[2]), left]));
+
+        // Wrong leaf, wrong proof or unknown batch fail
+        let outsider = H256::from_low_u64_be(5);
+        assert!(!CredentialRegistry::verify_batch_inclusion(&id, outsider, &[hash_leaf(leaves[3]), left]));
+        assert!(!CredentialRegistry::verify_batch_inclusion(&id, leaves[2], &[hash_leaf(leaves[1]), left]));
+        assert!(!CredentialRegistry::verify_batch_inclusion(b"unknown", leaves[2], &[hash_leaf(leaves[3]), left]));
+    })
+}
+
+#[test]
+fn batch_inclusion_rejects_internal_nodes() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = TEST_BATCH_ID.as_bytes().to_owned();
+        let schema_id = register_test_schema(owner);
+
+        let leaves: Vec<H256> = (1..=4).map(H256::from_low_u64_be).collect();
+        let left = hash_pair(hash_leaf(leaves[0]), hash_leaf(leaves[1]));
+        let right = hash_pair(hash_leaf(leaves[2]), hash_leaf(leaves[3]));
+        let root = hash_pair(left, right);
+        assert_ok!(CredentialRegistry::register_credential_batch(
+            Origin::signed(owner),
+            id.clone(),
+            owner,
+            root,
+            schema_id,
+            4,
+        ));
+
+        // The root itself with an empty proof
+        assert!(!CredentialRegistry::verify_batch_inclusion(&id, root, &[]));
+        // An internal node as a leaf, with the shorter proof that reaches the root
+        assert!(!CredentialRegistry::verify_batch_inclusion(&id, left, &[right]));
+        // A leaf with a proof longer than the tree
+        assert!(!CredentialRegistry::verify_batch_inclusion(&id, leaves[0], &[hash_leaf(leaves[1]), right, left]));
+    })
+}
+
+#[test]
+fn batch_proof_length_follows_count() {
+    assert_eq!(merkle_proof_length(1), 0);
+    assert_eq!(merkle_proof_length(2), 1);
+    assert_eq!(merkle_proof_length(4), 2);
+    assert_eq!(merkle_proof_length(5), 3);
+    assert_eq!(merkle_proof_length(BATCH_MAX_CREDENTIALS), 20);
+}
+
+#[test]
+fn register_batch_with_invalid_count() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let schema_id = register_test_schema(owner);
+        for count in vec![0, BATCH_MAX_CREDENTIALS + 1] {
+            assert_noop!(
+                CredentialRegistry::register_credential_batch(
+                    Origin::signed(owner),
+                    TEST_BATCH_ID.as_bytes().to_owned(),
+                    owner,
+                    H256::from_low_u64_be(1),
+                    schema_id.clone(),
+                    count,
+                ),
+                Error::<Test>::BatchInvalidCount
+            );
+        }
+    })
+}
+
+#[test]
+fn register_batch_by_non_issuer() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let schema_id = register_test_schema(owner);
+        assert_noop!(
+            CredentialRegistry::register_credential_batch(
+                Origin::signed(account_key("Mallory")),
+                TEST_BATCH_ID.as_bytes().to_owned(),
+                owner,
+                H256::from_low_u64_be(1),
+                schema_id,
+                2,
+            ),
+            Error::<Test>::CredNotIssuer
+        );
+    })
+}
+
+#[test]
+fn register_for_other_organization() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let other = account_key("Other Org");
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let schema_id = register_test_schema(owner);
+        add_test_issuer(other, account_key(TEST_SENDER));
+
+        assert_noop!(
+            CredentialRegistry::register_credential(
+                Origin::signed(account_key(TEST_SENDER)),
+                id.clone(),
+                other,
+                H256::from_low_u64_be(1),
+                None,
+                schema_id.clone(),
+                None,
+                None,
+                None,
+                None,
+                other,
+                issuer_signature(&id, &H256::from_low_u64_be(1), &schema_id),
+            ),
+            Error::<Test>::CredNotActingOrganization
+        );
+        assert_noop!(
+            CredentialRegistry::register_credential_batch(
+                Origin::signed(account_key(TEST_SENDER)),
+                TEST_BATCH_ID.as_bytes().to_owned(),
+                other,
+                H256::from_low_u64_be(1),
+                schema_id,
+                2,
+            ),
+            Error::<Test>::CredNotActingOrganization
+        );
+    })
+}
+
+#[test]
+fn register_batch_with_existing_id() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let schema_id = register_test_schema(owner);
+        assert_ok!(CredentialRegistry::register_credential_batch(
+            Origin::signed(owner),
+            TEST_BATCH_ID.as_bytes().to_owned(),
+            owner,
+            H256::from_low_u64_be(1),
+            schema_id.clone(),
+            2,
+        ));
+        assert_noop!(
+            CredentialRegistry::register_credential_batch(
+                Origin::signed(owner),
+                TEST_BATCH_ID.as_bytes().to_owned(),
+                owner,
+                H256::from_low_u64_be(2),
+                schema_id,
+                2,
+            ),
+            Error::<Test>::BatchIdExists
+        );
+    })
+}
+
+#[test]
+fn holder_acknowledges_credential() {
+    new_test_ext().execute_with(|| {
+        let sender = account_key(TEST_SENDER);
+        let owner = account_key(TEST_ORGANIZATION);
+        let holder = account_key("Holder");
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let other_id = b"00012345600099".to_vec();
+        let schema_id = register_test_schema(owner);
+
+        for cred_id in vec![&id, &other_id] {
+            assert_ok!(CredentialRegistry::register_credential(
+                Origin::signed(sender),
+                cred_id.clone(),
+                owner,
+                H256::from_low_u64_be(1),
+                None,
+                schema_id.clone(),
+                Some(holder),
+                None,
+                None,
+                None,
+                account_key(TEST_ORGANIZATION),
+                issuer_signature(&cred_id, &H256::from_low_u64_be(1), &schema_id),
+            ));
+        }
+
+        assert_eq!(
+            CredentialRegistry::credentials_of_holder(holder),
+            vec![id.clone(), other_id.clone()]
+        );
+        assert_eq!(
+            CredentialRegistry::cred_by_id(&id).unwrap().holder(),
+            Some(&holder)
+        );
+
+        assert_ok!(CredentialRegistry::acknowledge_credential(Origin::signed(holder), id.clone()));
+        assert_ok!(CredentialRegistry::reject_credential(Origin::signed(holder), other_id.clone()));
+
+        assert_eq!(
+            CredentialRegistry::holder_response(&id),
+            Some(HolderResponse::Acknowledged)
+        );
+        assert_eq!(
+            CredentialRegistry::holder_response(&other_id),
+            Some(HolderResponse::Rejected)
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::credential_registry(RawEvent::CredentialAcknowledged(holder, id.clone()))));
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::credential_registry(RawEvent::CredentialRejected(holder, other_id.clone()))));
+
+        assert_noop!(
+            CredentialRegistry::reject_credential(Origin::signed(holder), id),
+            Error::<Test>::CredHolderResponded
+        );
+    })
+}
+
+#[test]
+fn only_holder_can_respond() {
+    new_test_ext().execute_with(|| {
+        let sender = account_key(TEST_SENDER);
+        let owner = account_key(TEST_ORGANIZATION);
+        let holder = account_key("Holder");
+        let schema_id = register_test_schema(owner);
+
+        assert_ok!(CredentialRegistry::register_credential(
+            Origin::signed(sender),
+            TEST_CRED_ID.as_bytes().to_owned(),
+            owner,
+            H256::from_low_u64_be(1),
+            None,
+            schema_id.clone(),
+            Some(holder),
+            None,
+            None,
+            None,
+            account_key(TEST_ORGANIZATION),
+            issuer_signature(TEST_CRED_ID.as_bytes(), &H256::from_low_u64_be(1), &schema_id),
+        ));
+        assert_noop!(
+            CredentialRegistry::acknowledge_credential(
+                Origin::signed(sender),
+                TEST_CRED_ID.as_bytes().to_owned()
+            ),
+            Error::<Test>::CredNotHolder
+        );
+
+        // A credential without a holder can't be answered
+        let unbound_id = b"00012345600099".to_vec();
+        assert_ok!(CredentialRegistry::register_credential(
+            Origin::signed(sender),
+            unbound_id.clone(),
+            owner,
+            H256::from_low_u64_be(2),
+            None,
+            schema_id.clone(),
+            None,
+            None,
+            None,
+            None,
+            account_key(TEST_ORGANIZATION),
+            issuer_signature(&unbound_id, &H256::from_low_u64_be(2), &schema_id),
+        ));
+        assert_noop!(
+            CredentialRegistry::acknowledge_credential(Origin::signed(holder), unbound_id),
+            Error::<Test>::CredNotHolder
+        );
+    })
+}
+
+#[test]
+fn register_cred_signed_by_delegate_key() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let signer = account_pair(TEST_SENDER);
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let hash = H256::from_low_u64_be(1);
+        let schema_id = register_test_schema(owner);
+        assert_ok!(DID::add_delegate(
+            Origin::signed(owner),
+            owner,
+            signer.public(),
+            b"x25519VerificationKey2018".to_vec(),
+            None,
+        ));
+
+        let payload = CredentialRegistry::credential_signing_payload(&id, &hash, &None, &schema_id);
+        assert_ok!(CredentialRegistry::register_credential(
+            Origin::signed(owner),
+            id.clone(),
+            owner,
+            hash,
+            None,
+            schema_id,
+            None,
+            None,
+            None,
+            None,
+            signer.public(),
+            signer.sign(&payload),
+        ));
+
+        let cred = CredentialRegistry::cred_by_id(&id).unwrap();
+        assert_eq!(cred.signer(), &signer.public());
+    });
+}
+
+#[test]
+fn register_cred_with_bad_signature() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let hash = H256::from_low_u64_be(1);
+        let schema_id = register_test_schema(owner);
+
+        // Signature over a different hash
+        assert_noop!(
+            CredentialRegistry::register_credential(
+                Origin::signed(owner),
+                id.clone(),
+                owner,
+                hash,
+                None,
+                schema_id.clone(),
+                None,
+                None,
+                None,
+                None,
+                owner,
+                issuer_signature(&id, &H256::from_low_u64_be(2), &schema_id),
+            ),
+            Error::<Test>::CredBadSignature
+        );
+
+        // Valid signature from a key that is not an assertion key of the issuer
+        let outsider = account_pair(TEST_SENDER);
+        let payload = CredentialRegistry::credential_signing_payload(&id, &hash, &None, &schema_id);
+        assert_noop!(
+            CredentialRegistry::register_credential(
+                Origin::signed(owner),
+                id.clone(),
+                owner,
+                hash,
+                None,
+                schema_id,
+                None,
+                None,
+                None,
+                None,
+                outsider.public(),
+                outsider.sign(&payload),
+            ),
+            Error::<Test>::CredBadSignature
+        );
+    });
+}
+
+#[test]
+fn verify_credential_by_id_and_hash() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        Timestamp::set_timestamp(42);
+        let id = register_test_credential(account_key(TEST_SENDER), owner);
+        let schema_id = TEST_SCHEMA_ID.as_bytes().to_owned();
+
+        let verdict = CredentialVerification {
+            id: id.clone(),
+            issuer: owner,
+            signer: owner,
+            claims_root: None,
+            schema_id,
+            registered: 42,
+            status: CredentialStatus::Active,
+            valid: true,
+        };
+        assert_eq!(
+            CredentialRegistry::verify_credential(CredentialLookup::Id(id.clone())),
+            vec![verdict.clone()]
+        );
+        assert_eq!(
+            CredentialRegistry::verify_credential(CredentialLookup::Hash(H256::from_low_u64_be(1))),
+            vec![verdict]
+        );
+
+        assert_ok!(CredentialRegistry::revoke_credential(
+            Origin::signed(owner),
+            id.clone(),
+            owner,
+            H256::from_low_u64_be(1),
+            None,
+        ));
+        let revoked = CredentialRegistry::verify_credential(CredentialLookup::Id(id));
+        assert_eq!(revoked[0].status, CredentialStatus::Revoked);
+        assert!(!revoked[0].valid);
+
+        assert!(CredentialRegistry::verify_credential(CredentialLookup::Hash(H256::from_low_u64_be(9))).is_empty());
+    });
+}
+
+#[test]
+fn create_cred_with_invalid_prop_keys() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let hash = H256::from_low_u64_be(1);
+        let schema_id = register_test_schema(owner);
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+
+        for (props, error) in vec![
+            (
+                vec![CredProperty::new(b"", ClaimValue::Boolean(true))],
+                Error::<Test>::CredInvalidPropKey,
+            ),
+            (
+                vec![CredProperty::new(b"a-very-long-property-key", ClaimValue::Boolean(true))],
+                Error::<Test>::CredInvalidPropKey,
+            ),
+            (
+                vec![
+                    CredProperty::new(b"grade", ClaimValue::Integer(7)),
+                    CredProperty::new(b"grade", ClaimValue::Integer(9)),
+                ],
+                Error::<Test>::CredDuplicatePropKey,
+            ),
+        ] {
+            assert_noop!(
+                CredentialRegistry::register_credential(
+                    Origin::signed(owner),
+                    id.clone(),
+                    owner,
+                    hash,
+                    None,
+                    schema_id.clone(),
+                    None,
+                    Some(props),
+                    None,
+                    None,
+                    owner,
+                    issuer_signature(&id, &hash, &schema_id),
+                ),
+                error
+            );
+        }
+    });
+}
+
+#[test]
+fn create_cred_with_props_declared_by_schema() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let hash = H256::from_low_u64_be(1);
+        let schema_id = TEST_SCHEMA_ID.as_bytes().to_owned();
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        assert_ok!(SchemaRegistry::register_schema(
+            Origin::signed(owner),
+            schema_id.clone(),
+            H256::from_low_u64_be(100),
+            Some(vec![SchemaProperty::new(b"grade", b"Final grade", b"1.0")]),
+        ));
+
+        assert_noop!(
+            CredentialRegistry::register_credential(
+                Origin::signed(owner),
+                id.clone(),
+                owner,
+                hash,
+                None,
+                schema_id.clone(),
+                None,
+                Some(vec![CredProperty::new(b"subject", ClaimValue::Text(b"Ashok".to_vec()))]),
+                None,
+                None,
+                owner,
+                issuer_signature(&id, &hash, &schema_id),
+            ),
+            Error::<Test>::CredPropNotInSchema
+        );
+
+        // Declared keys are accepted, including selectively hashed values
+        let hashed_grade = ClaimValue::Hashed(BlakeTwo256::hash(b"A+").into());
+        assert_ok!(CredentialRegistry::register_credential(
+            Origin::signed(owner),
+            id.clone(),
+            owner,
+            hash,
+            None,
+            schema_id.clone(),
+            None,
+            Some(vec![CredProperty::new(b"grade", hashed_grade.clone())]),
+            None,
+            None,
+            owner,
+            issuer_signature(&id, &hash, &schema_id),
+        ));
+        let props = CredentialRegistry::cred_by_id(&id).unwrap().props.unwrap();
+        assert_eq!(props[0].value(), &hashed_grade);
+        assert!(props[0].value().is_hashed());
+    });
+}
+
+#[test]
+fn disclosed_claims_verify_against_anchored_root() {
+    use crate::disclosure::{claim_hash, claims_root, disclose, verify_disclosure};
+
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let hash = H256::from_low_u64_be(1);
+        let schema_id = register_test_schema(owner);
+        let claims = vec![
+            (CredProperty::new(b"name", ClaimValue::Text(b"Ashok".to_vec())), [1u8; 32]),
+            (CredProperty::new(b"grade", ClaimValue::Integer(9)), [2u8; 32]),
+            (CredProperty::new(b"passed", ClaimValue::Boolean(true)), [3u8; 32]),
+        ];
+        let root = claims_root::<BlakeTwo256>(&claims);
+
+        let payload = CredentialRegistry::credential_signing_payload(&id, &hash, &root, &schema_id);
+        assert_ok!(CredentialRegistry::register_credential(
+            Origin::signed(owner),
+            id.clone(),
+            owner,
+            hash,
+            root,
+            schema_id,
+            None,
+            None,
+            None,
+            None,
+            owner,
+            account_pair(TEST_ORGANIZATION).sign(&payload),
+        ));
+        let anchored = *CredentialRegistry::cred_by_id(&id).unwrap().claims_root().unwrap();
+
+        // Reveal the grade only, then both the name and the last claim
+        let grade = disclose::<BlakeTwo256>(&claims, &[1]).unwrap();
+        assert!(verify_disclosure::<BlakeTwo256>(&grade, &anchored));
+        let name_and_passed = disclose::<BlakeTwo256>(&claims, &[0, 2]).unwrap();
+        assert!(verify_disclosure::<BlakeTwo256>(&name_and_passed, &anchored));
+
+        // A tampered value or salt does not verify
+        let mut forged = grade.clone();
+        forged[0].claim = CredProperty::new(b"grade", ClaimValue::Integer(10));
+        assert!(!verify_disclosure::<BlakeTwo256>(&forged, &anchored));
+        let mut resalted = grade;
+        resalted[0].salt = [9u8; 32];
+        assert!(!verify_disclosure::<BlakeTwo256>(&resalted, &anchored));
+
+        assert!(disclose::<BlakeTwo256>(&claims, &[3]).is_none());
+
+        // An internal node can't stand in for a claim leaf
+        let name = disclose::<BlakeTwo256>(&claims, &[0]).unwrap();
+        let leaf = merkle_leaf::<BlakeTwo256>(claim_hash::<BlakeTwo256>(&name[0].claim, &name[0].salt).as_ref());
+        let internal = hash_sorted_pair::<BlakeTwo256>(leaf, name[0].proof[0]);
+        assert_eq!(hash_sorted_pair::<BlakeTwo256>(internal, name[0].proof[1]), anchored);
+        assert_ne!(
+            merkle_root_from_proof::<BlakeTwo256>(merkle_leaf::<BlakeTwo256>(internal.as_ref()), &name[0].proof[1..]),
+            anchored
+        );
+    });
+}
+
+#[test]
+fn register_with_claims_root_and_props() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let hash = H256::from_low_u64_be(1);
+        let schema_id = register_test_schema(owner);
+        let root = Some(H256::from_low_u64_be(2));
+
+        let payload = CredentialRegistry::credential_signing_payload(&id, &hash, &root, &schema_id);
+        assert_noop!(
+            CredentialRegistry::register_credential(
+                Origin::signed(owner),
+                id.clone(),
+                owner,
+                hash,
+                root,
+                schema_id,
+                None,
+                Some(vec![CredProperty::new(b"grade", ClaimValue::Integer(9))]),
+                None,
+                None,
+                owner,
+                account_pair(TEST_ORGANIZATION).sign(&payload),
+            ),
+            Error::<Test>::CredPropsWithClaimsRoot
+        );
+    });
+}
+
+#[test]
+fn verifier_records_presentation_with_holder_consent() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let holder = account_pair("Holder");
+        let verifier = account_key("Verifier");
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let hash = H256::from_low_u64_be(1);
+        let schema_id = register_test_schema(owner);
+        let purpose = b"KYC onboarding".to_vec();
+        Timestamp::set_timestamp(42);
+
+        assert_ok!(CredentialRegistry::register_credential(
+            Origin::signed(owner),
+            id.clone(),
+            owner,
+            hash,
+            None,
+            schema_id.clone(),
+            Some(holder.public()),
+            None,
+            None,
+            None,
+            owner,
+            issuer_signature(&id, &hash, &schema_id),
+        ));
+
+        // Consent given to another verifier can't be reused
+        let payload = CredentialRegistry::presentation_consent_payload(&id, &owner, &purpose, &10);
+        assert_noop!(
+            CredentialRegistry::record_presentation(
+                Origin::signed(verifier),
+                id.clone(),
+                purpose.clone(),
+                10,
+                holder.sign(&payload)
+            ),
+            Error::<Test>::PresentationBadConsent
+        );
+
+        let payload = CredentialRegistry::presentation_consent_payload(&id, &verifier, &purpose, &10);
+        let consent = holder.sign(&payload);
+        assert_ok!(CredentialRegistry::record_presentation(
+            Origin::signed(verifier),
+            id.clone(),
+            purpose.clone(),
+            10,
+            consent.clone()
+        ));
+
+        assert_eq!(
+            CredentialRegistry::presentations_of_holder(holder.public()),
+            vec![Presentation::new(id.clone(), verifier, consent, purpose, 42)]
+        );
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::credential_registry(RawEvent::PresentationRecorded(
+                verifier,
+                id.clone(),
+                holder.public()
+            ))));
+    });
+}
+
+#[test]
+fn presentation_consent_is_single_use_and_expires() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let holder = account_pair("Holder");
+        let verifier = account_key("Verifier");
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let hash = H256::from_low_u64_be(1);
+        let schema_id = register_test_schema(owner);
+        let purpose = b"KYC onboarding".to_vec();
+
+        assert_ok!(CredentialRegistry::register_credential(
+            Origin::signed(owner),
+            id.clone(),
+            owner,
+            hash,
+            None,
+            schema_id.clone(),
+            Some(holder.public()),
+            None,
+            None,
+            None,
+            owner,
+            issuer_signature(&id, &hash, &schema_id),
+        ));
+
+        let payload = CredentialRegistry::presentation_consent_payload(&id, &verifier, &purpose, &10);
+        let consent = holder.sign(&payload);
+        assert_ok!(CredentialRegistry::record_presentation(
+            Origin::signed(verifier),
+            id.clone(),
+            purpose.clone(),
+            10,
+            consent.clone()
+        ));
+
+        // Replaying the same signature fails
+        assert_noop!(
+            CredentialRegistry::record_presentation(
+                Origin::signed(verifier),
+                id.clone(),
+                purpose.clone(),
+                10,
+                consent.clone()
+            ),
+            Error::<Test>::PresentationConsentUsed
+        );
+        assert_eq!(CredentialRegistry::presentations_of_holder(holder.public()).len(), 1);
+
+        // The used consent is forgotten once it expired
+        let consent_key = BlakeTwo256::hash(&payload);
+        assert!(CredentialRegistry::consent_used(consent_key));
+        assert_eq!(CredentialRegistry::consents_expiring(10), vec![consent_key]);
+        System::set_block_number(10);
+        CredentialRegistry::on_initialize(10);
+        assert!(!CredentialRegistry::consent_used(consent_key));
+        assert!(CredentialRegistry::consents_expiring(10).is_empty());
+
+        // A consent past its expiry block fails
+        let payload = CredentialRegistry::presentation_consent_payload(&id, &verifier, &purpose, &20);
+        System::set_block_number(20);
+        assert_noop!(
+            CredentialRegistry::record_presentation(
+                Origin::signed(verifier),
+                id,
+                purpose,
+                20,
+                holder.sign(&payload)
+            ),
+            Error::<Test>::PresentationConsentExpired
+        );
+    });
+}
+
+#[test]
+fn presentation_consents_expiring_per_block_are_capped() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let holder = account_pair("Holder");
+        let verifier = account_key("Verifier");
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let hash = H256::from_low_u64_be(1);
+        let schema_id = register_test_schema(owner);
+
+        assert_ok!(CredentialRegistry::register_credential(
+            Origin::signed(owner),
+            id.clone(),
+            owner,
+            hash,
+            None,
+            schema_id.clone(),
+            Some(holder.public()),
+            None,
+            None,
+            None,
+            owner,
+            issuer_signature(&id, &hash, &schema_id),
+        ));
+        ConsentsExpiring::<Test>::insert(10, vec![H256::zero(); MAX_CONSENT_EXPIRIES_PER_BLOCK]);
+
+        let payload = CredentialRegistry::presentation_consent_payload(&id, &verifier, b"audit", &10);
+        assert_noop!(
+            CredentialRegistry::record_presentation(
+                Origin::signed(verifier),
+                id.clone(),
+                b"audit".to_vec(),
+                10,
+                holder.sign(&payload)
+            ),
+            Error::<Test>::PresentationConsentBlockFull
+        );
+
+        let payload = CredentialRegistry::presentation_consent_payload(&id, &verifier, b"audit", &11);
+        assert_ok!(CredentialRegistry::record_presentation(
+            Origin::signed(verifier),
+            id,
+            b"audit".to_vec(),
+            11,
+            holder.sign(&payload)
+        ));
+    });
+}
+
+#[test]
+fn presentation_requires_a_holder() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let verifier = account_key("Verifier");
+        let id = register_test_credential(account_key(TEST_SENDER), owner);
+        let payload = CredentialRegistry::presentation_consent_payload(&id, &verifier, b"audit", &10);
+
+        assert_noop!(
+            CredentialRegistry::record_presentation(
+                Origin::signed(verifier),
+                id,
+                b"audit".to_vec(),
+                10,
+                account_pair(TEST_ORGANIZATION).sign(&payload)
+            ),
+            Error::<Test>::CredHolderMissing
+        );
+    });
+}
+
+fn register_held_credential(id: &[u8], holder: <Test as system::Trait>::AccountId) {
+    let owner = account_key(TEST_ORGANIZATION);
+    let hash = H256::from_low_u64_be(1);
+    let schema_id = TEST_SCHEMA_ID.as_bytes().to_owned();
+    assert_ok!(CredentialRegistry::register_credential(
+        Origin::signed(owner),
+        id.to_vec(),
+        owner,
+        hash,
+        None,
+        schema_id.clone(),
+        Some(holder),
+        None,
+        None,
+        None,
+        owner,
+        issuer_signature(id, &hash, &schema_id),
+    ));
+}
+
+#[test]
+fn holder_transfers_credential_of_transferable_schema() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let holder = account_key("Holder");
+        let buyer = account_key("Buyer");
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let schema_id = register_test_schema(owner);
+        register_held_credential(&id, holder);
+        Timestamp::set_timestamp(42);
+
+        assert_noop!(
+            CredentialRegistry::transfer_credential(Origin::signed(holder), id.clone(), buyer),
+            Error::<Test>::CredNotTransferable
+        );
+        assert_ok!(SchemaRegistry::set_schema_transferable(Origin::signed(owner), schema_id, true));
+        assert_noop!(
+            CredentialRegistry::transfer_credential(Origin::signed(buyer), id.clone(), buyer),
+            Error::<Test>::CredNotHolder
+        );
+        assert_ok!(CredentialRegistry::acknowledge_credential(Origin::signed(holder), id.clone()));
+
+        assert_ok!(CredentialRegistry::transfer_credential(Origin::signed(holder), id.clone(), buyer));
+
+        assert_eq!(CredentialRegistry::cred_by_id(&id).unwrap().holder(), Some(&buyer));
+        assert!(CredentialRegistry::credentials_of_holder(holder).is_empty());
+        assert_eq!(CredentialRegistry::credentials_of_holder(buyer), vec![id.clone()]);
+        assert_eq!(CredentialRegistry::holder_history(&id), vec![(holder, 42)]);
+        assert_eq!(CredentialRegistry::holder_response(&id), None);
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::credential_registry(RawEvent::CredentialTransferred(holder, id.clone(), buyer))));
+
+        assert_noop!(
+            CredentialRegistry::transfer_credential(Origin::signed(buyer), id, buyer),
+            Error::<Test>::CredSameHolder
+        );
+    });
+}
+
+#[test]
+fn issuer_reissues_credential_under_new_id() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let holder = account_key("Holder");
+        let id = TEST_CRED_ID.as_bytes().to_owned();
+        let new_id = b"00012345600013".to_vec();
+        let new_hash = H256::from_low_u64_be(2);
+        let schema_id = register_test_schema(owner);
+        register_held_credential(&id, holder);
+
+        // An invalid replacement leaves the old credential untouched
+        assert_noop!(
+            CredentialRegistry::reissue_credential(
+                Origin::signed(owner),
+                id.clone(),
+                new_id.clone(),
+                new_hash,
+                None,
+                Some(holder),
+                None,
+                None,
+                None,
+                owner,
+                issuer_signature(&new_id, &H256::from_low_u64_be(3), &schema_id),
+                Some(7),
+            ),
+            Error::<Test>::CredBadSignature
+        );
+        assert_noop!(
+            CredentialRegistry::reissue_credential(
+                Origin::signed(account_key(TEST_SENDER)),
+                id.clone(),
+                new_id.clone(),
+                new_hash,
+                None,
+                Some(holder),
+                None,
+                None,
+                None,
+                owner,
+                issuer_signature(&new_id, &new_hash, &schema_id),
+                Some(7),
+            ),
+            Error::<Test>::CredNotIssuer
+        );
+
+        assert_ok!(CredentialRegistry::reissue_credential(
+            Origin::signed(owner),
+            id.clone(),
+            new_id.clone(),
+            new_hash,
+            None,
+            Some(holder),
+            None,
+            None,
+            None,
+            owner,
+            issuer_signature(&new_id, &new_hash, &schema_id),
+            Some(7),
+        ));
+
+        assert_eq!(CredentialRegistry::credential_status(&id), Some(CredentialStatus::Revoked));
+        assert_eq!(CredentialRegistry::revocation_of(&id).unwrap().reason(), Some(7));
+        assert_eq!(CredentialRegistry::credential_status(&new_id), Some(CredentialStatus::Active));
+        assert_eq!(CredentialRegistry::replaced_by(&id), Some(new_id.clone()));
+        assert_eq!(CredentialRegistry::replaces(&new_id), Some(id.clone()));
+        assert_eq!(CredentialRegistry::credentials_of_holder(holder), vec![id.clone(), new_id.clone()]);
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::credential_registry(RawEvent::CredentialReissued(owner, id.clone(), new_id.clone()))));
+
+        assert_noop!(
+            CredentialRegistry::reissue_credential(
+                Origin::signed(owner),
+                id,
+                b"00012345600014".to_vec(),
+                new_hash,
+                None,
+                None,
+                None,
+                None,
+                None,
+                owner,
+                issuer_signature(b"00012345600014", &new_hash, &schema_id),
+                None,
+            ),
+            Error::<Test>::CredAlreadyRevoked
+        );
+    });
+}
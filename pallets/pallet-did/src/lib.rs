@@ -67,6 +67,10 @@
 //! * `valid_delegate` - Validates if a delegate belongs to an identity and it has not expired.
 //!    The identity owner has all provileges and is considered as delegate with all permissions.
 //! * `valid_listed_delegate` - Returns a boolean value. `True` if the `delegate` belongs the `identity` delegates list.
+//! * `expire_delegate` - Revokes a delegate of an `identity` on behalf of its owner, e.g. from another pallet.
+//! * `renew_delegate` - Moves the expiry of a valid delegate of an `identity` to a later block.
+//! * `set_owner` - Transfers an `identity` to a new owner on behalf of another pallet, without an origin check.
+//! * `renew_attribute` - Replaces the value and validity of an existing attribute, e.g. to re-validate a revoked one.
 //! * `valid_attribute` - Validates if an attribute belongs to an identity and it has not expired.
 //! * `attribute_and_id` - Get the `attribute` and its `hash` identifier.
 //! * `check_signature` - Validates the signer from a signature.
@@ -154,26 +158,7 @@ decl_module! {
             let who = ensure_signed(origin)?;
             Self::is_owner(&identity, &who)?;
 
-            let now_timestamp = <pallet_timestamp::Module<T>>::now();
-            let now_block_number = <frame_system::Module<T>>::block_number();
-
-            if <OwnerOf<T>>::contains_key(&identity) {
-                // Update to new owner.
-                <OwnerOf<T>>::mutate(&identity, |o| *o = Some(new_owner.clone()));
-            } else {
-                // Add to new owner.
-                <OwnerOf<T>>::insert(&identity, &new_owner);
-            }
-            // Save the update time and block.
-            <UpdatedBy<T>>::insert(
-                &identity, (&who, &now_block_number, &now_timestamp),
-            );
-            Self::deposit_event(RawEvent::OwnerChanged(
-                identity,
-                who,
-                new_owner,
-                now_block_number,
-            ));
+            Self::set_owner(&identity, &new_owner);
             Ok(())
         }
 
@@ -213,17 +198,12 @@ decl_module! {
             delegate: T::AccountId,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            Self::is_owner(&identity, &who)?;
-            Self::valid_listed_delegate(&identity, &delegate_type, &delegate)?;
             ensure!(delegate_type.len() <= 64, Error::<T>::InvalidDelegate);
 
+            Self::expire_delegate(&who, &identity, &delegate, &delegate_type)?;
+
             let now_timestamp = <pallet_timestamp::Module<T>>::now();
             let now_block_number = <frame_system::Module<T>>::block_number();
-
-            // Update only the validity period to revoke the delegate.
-            <DelegateOf<T>>::mutate(
-                (&identity, &delegate_type, &delegate), |b| *b = Some(now_block_number),
-            );
             <UpdatedBy<T>>::insert(&identity, (who, now_block_number, now_timestamp));
             Self::deposit_event(RawEvent::DelegateRevoked(identity, delegate_type, delegate));
             Ok(())
@@ -418,6 +398,64 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    // Expires a delegate of an account at the current block.
+    pub fn expire_delegate(
+        who: &T::AccountId,
+        identity: &T::AccountId,
+        delegate: &T::AccountId,
+        delegate_type: &[u8],
+    ) -> DispatchResult {
+        Self::is_owner(&identity, who)?;
+        Self::valid_listed_delegate(identity, delegate_type, delegate)?;
+
+        // Update only the validity period to revoke the delegate.
+        let now_block_number = <frame_system::Module<T>>::block_number();
+        <DelegateOf<T>>::mutate(
+            (identity, delegate_type, delegate), |b| *b = Some(now_block_number),
+        );
+        Ok(())
+    }
+
+    /// Transfers ownership of an identity without checking who asks for it, e.g. for identities
+    /// managed by another pallet that nobody holds the key to. The previous owner is recorded
+    /// as the updater.
+    pub fn set_owner(identity: &T::AccountId, new_owner: &T::AccountId) {
+        let previous_owner = Self::identity_owner(identity);
+        let now_timestamp = <pallet_timestamp::Module<T>>::now();
+        let now_block_number = <frame_system::Module<T>>::block_number();
+
+        <OwnerOf<T>>::insert(identity, new_owner);
+        // Save the update time and block.
+        <UpdatedBy<T>>::insert(
+            identity, (&previous_owner, &now_block_number, &now_timestamp),
+        );
+        Self::deposit_event(RawEvent::OwnerChanged(
+            identity.clone(),
+            previous_owner,
+            new_owner.clone(),
+            now_block_number,
+        ));
+    }
+
+    /// Sets the block until which a valid delegate of an account remains valid.
+    pub fn renew_delegate(
+        who: &T::AccountId,
+        identity: &T::AccountId,
+        delegate: &T::AccountId,
+        delegate_type: &[u8],
+        valid_until: T::BlockNumber,
+    ) -> DispatchResult {
+        Self::is_owner(&identity, who)?;
+        Self::valid_listed_delegate(identity, delegate_type, delegate)?;
+        ensure!(
+            valid_until > <frame_system::Module<T>>::block_number(),
+            Error::<T>::InvalidDelegate
+        );
+
+        <DelegateOf<T>>::insert((identity, delegate_type, delegate), &valid_until);
+        Ok(())
+    }
+
     /// Checks if a signature is valid. Used to validate off-chain transactions.
     pub fn check_signature(
         signature: &T::Signature,
@@ -521,6 +559,35 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Replaces the value and validity of the latest attribute with that name, keeping its identifier.
+    /// Lets an attribute revoked with `reset_attribute` be made valid again.
+    pub fn renew_attribute(
+        who: T::AccountId,
+        identity: &T::AccountId,
+        name: &[u8],
+        value: &[u8],
+        valid_for: Option<T::BlockNumber>,
+    ) -> DispatchResult {
+        Self::is_owner(&identity, &who)?;
+        let (mut attribute, id) =
+            Self::attribute_and_id(identity, name).ok_or(Error::<T>::AttributeResetFailed)?;
+        let now_block_number = <frame_system::Module<T>>::block_number();
+
+        attribute.value = value.to_vec();
+        attribute.validity = match valid_for {
+            Some(blocks) => now_block_number + blocks,
+            None => u32::max_value().into(),
+        };
+        <AttributeOf<T>>::insert((&identity, id), attribute);
+
+        // Keep track of the updates.
+        <UpdatedBy<T>>::insert(
+            identity,
+            (who, now_block_number, <pallet_timestamp::Module<T>>::now()),
+        );
+        Ok(())
+    }
+
     /// Validates if an attribute belongs to an identity and it has not expired.
     pub fn valid_attribute(identity: &T::AccountId, name: &[u8], value: &[u8]) -> DispatchResult {
         ensure!(name.len() <= 64, Error::<T>::InvalidAttribute);
@@ -208,3 +208,98 @@ fn attacker_add_new_delegate_should_fail() {
         );
     });
 }
+
+#[test]
+fn owner_expires_delegate() {
+    new_test_ext().execute_with(|| {
+        let alice = account_key("Alice");
+        let bob = account_key("Bob");
+        let delegate_type = b"OrgMember".to_vec();
+
+        assert_ok!(DID::create_delegate(&alice, &alice, &bob, &delegate_type, None));
+        assert_ok!(DID::valid_delegate(&alice, &delegate_type, &bob));
+
+        // Only the identity owner can expire its delegates.
+        assert_noop!(
+            DID::expire_delegate(&bob, &alice, &bob, &delegate_type),
+            Error::<Test>::NotOwner
+        );
+
+        assert_ok!(DID::expire_delegate(&alice, &alice, &bob, &delegate_type));
+        assert_noop!(
+            DID::valid_delegate(&alice, &delegate_type, &bob),
+            Error::<Test>::InvalidDelegate
+        );
+        assert_noop!(
+            DID::expire_delegate(&alice, &alice, &bob, &delegate_type),
+            Error::<Test>::InvalidDelegate
+        );
+    });
+}
+
+#[test]
+fn owner_renews_delegate() {
+    new_test_ext().execute_with(|| {
+        let alice = account_key("Alice");
+        let bob = account_key("Bob");
+        let delegate_type = b"OrgMember".to_vec();
+
+        assert_ok!(DID::create_delegate(&alice, &alice, &bob, &delegate_type, Some(5)));
+        assert_noop!(
+            DID::renew_delegate(&bob, &alice, &bob, &delegate_type, 20),
+            Error::<Test>::NotOwner
+        );
+
+        assert_ok!(DID::renew_delegate(&alice, &alice, &bob, &delegate_type, 20));
+        System::set_block_number(10);
+        assert_ok!(DID::valid_delegate(&alice, &delegate_type, &bob));
+
+        System::set_block_number(20);
+        assert_noop!(
+            DID::renew_delegate(&alice, &alice, &bob, &delegate_type, 30),
+            Error::<Test>::InvalidDelegate
+        );
+    });
+}
+
+#[test]
+fn pallet_sets_owner() {
+    new_test_ext().execute_with(|| {
+        let alice = account_key("Alice");
+        let bob = account_key("Bob");
+
+        DID::set_owner(&alice, &bob);
+        assert_eq!(DID::identity_owner(&alice), bob);
+        assert_eq!(DID::updated_by(&alice).0, alice);
+        assert_noop!(DID::is_owner(&alice, &alice), Error::<Test>::NotOwner);
+    });
+}
+
+#[test]
+fn pallet_renews_revoked_attribute() {
+    new_test_ext().execute_with(|| {
+        let alice = account_key("Alice");
+        let bob = account_key("Bob");
+        let name = b"Org".to_vec();
+
+        assert_noop!(
+            DID::renew_attribute(alice.clone(), &alice, &name, b"Dhiway", None),
+            Error::<Test>::AttributeResetFailed
+        );
+        assert_ok!(DID::create_attribute(alice.clone(), &alice, &name, b"Dhiway", None));
+        assert_ok!(DID::reset_attribute(alice.clone(), &alice, &name));
+        assert!(DID::valid_attribute(&alice, &name, b"Dhiway").is_err());
+
+        // Creating the attribute again collides with the revoked one.
+        assert_noop!(
+            DID::create_attribute(alice.clone(), &alice, &name, b"Dhiway Networks", None),
+            Error::<Test>::AttributeCreationFailed
+        );
+        assert_noop!(
+            DID::renew_attribute(bob, &alice, &name, b"Dhiway Networks", None),
+            Error::<Test>::NotOwner
+        );
+        assert_ok!(DID::renew_attribute(alice.clone(), &alice, &name, b"Dhiway Networks", None));
+        assert_ok!(DID::valid_attribute(&alice, &name, b"Dhiway Networks"));
+    });
+}
@@ -8,7 +8,7 @@ use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, dispatch, ensure, sp_runtime::RuntimeDebug,
     sp_std::prelude::*, traits::EnsureOrigin,
 };
-use frame_system::{self as system, ensure_signed};
+use frame_system::{self as system};
 
 #[cfg(test)]
 mod mock;
@@ -42,6 +42,12 @@ pub struct Schema<AccountId, Hash, Moment> {
     registered: Moment,
 }
 
+impl<AccountId, Hash, Moment> Schema<AccountId, Hash, Moment> {
+    pub fn props(&self) -> Option<&Vec<SchemaProperty>> {
+        self.props.as_ref()
+    }
+}
+
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
 pub struct SchemaProperty {
     name: SchemaName,
@@ -73,7 +79,8 @@ impl SchemaProperty {
 
 pub trait Trait: frame_system::Trait + timestamp::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
-    type CreateRoleOrigin: EnsureOrigin<Self::Origin>;
+    /// Origin of organization members, resolving to the member and the organization it acts for.
+    type CreateRoleOrigin: EnsureOrigin<Self::Origin, Success = (Self::AccountId, Self::AccountId)>;
 }
 
 decl_storage! {
@@ -82,6 +89,8 @@ decl_storage! {
         pub SchemasOfOrganization get(fn schemas_of_org): map hasher(blake2_128_concat) T::AccountId => Vec<SchemaId>;
         pub OwnerOfSchema get(fn owner_of_schema): map hasher(blake2_128_concat) SchemaId => Option<T::AccountId>;
         pub SchemaByHash get(fn schema_by_hash):map hasher(opaque_blake2_256) T::Hash => Vec<SchemaId>;
+        pub DeactivatedSchemas get(fn schema_deactivated): map hasher(blake2_128_concat) SchemaId => bool;
+        pub TransferableSchemas get(fn schema_transferable): map hasher(blake2_128_concat) SchemaId => bool;
     }
 }
 
@@ -92,6 +101,8 @@ decl_event!(
         Hash = <T as frame_system::Trait>::Hash,
     {
         SchemaRegistered(AccountId, SchemaId, Hash),
+        SchemaDeactivated(AccountId, SchemaId),
+        SchemaTransferableSet(AccountId, SchemaId, bool),
     }
 );
 
@@ -106,6 +117,8 @@ decl_error! {
         SchemaInvalidName,
         SchemaInvalidDescription,
         SchemaInvalidVersion,
+        SchemaNotOwner,
+        SchemaAlreadyDeactivated,
     }
 }
 
@@ -115,12 +128,12 @@ decl_module! {
         fn deposit_event() = default;
 
         #[weight = 10]
-        pub fn register_schema(origin, id: SchemaId, owner: T::AccountId, hash: T::Hash, 
+        pub fn register_schema(origin, id: SchemaId, hash: T::Hash,
             props: Option<Vec<SchemaProperty>>) -> dispatch::DispatchResult {
 
-            T::CreateRoleOrigin::ensure_origin(origin.clone())?;
-            let who = ensure_signed(origin)?;
-            
+            // The schema is owned by the organization the member acts for
+            let (who, owner) = T::CreateRoleOrigin::ensure_origin(origin)?;
+
             // Validate schema ID
             Self::validate_schema_id(&id)?;
 
@@ -149,6 +162,51 @@ decl_module! {
 
             Ok(())
         }
+
+        #[weight = 10]
+        pub fn deactivate_schema(origin, id: SchemaId) -> dispatch::DispatchResult {
+
+            let (who, org) = T::CreateRoleOrigin::ensure_origin(origin)?;
+
+            // Validate schema ID
+            Self::validate_schema_id(&id)?;
+
+            // Only the owning organization may retire its schema (1 DB read)
+            let owner = Self::owner_of_schema(&id).ok_or(Error::<T>::SchemaIdMissing)?;
+            ensure!(owner == org, Error::<T>::SchemaNotOwner);
+            ensure!(!Self::schema_deactivated(&id), Error::<T>::SchemaAlreadyDeactivated);
+
+            // Keep the schema for existing references, only flag it (1 DB write)
+            <DeactivatedSchemas>::insert(&id, true);
+
+            Self::deposit_event(RawEvent::SchemaDeactivated(who, id));
+
+            Ok(())
+        }
+
+        #[weight = 10]
+        pub fn set_schema_transferable(origin, id: SchemaId, transferable: bool) -> dispatch::DispatchResult {
+
+            let (who, org) = T::CreateRoleOrigin::ensure_origin(origin)?;
+
+            // Validate schema ID
+            Self::validate_schema_id(&id)?;
+
+            // Only the owning organization may allow transfers (1 DB read)
+            let owner = Self::owner_of_schema(&id).ok_or(Error::<T>::SchemaIdMissing)?;
+            ensure!(owner == org, Error::<T>::SchemaNotOwner);
+
+            // Credentials of transferable schemas can change holder (1 DB write)
+            if transferable {
+                <TransferableSchemas>::insert(&id, true);
+            } else {
+                <TransferableSchemas>::remove(&id);
+            }
+
+            Self::deposit_event(RawEvent::SchemaTransferableSet(who, id, transferable));
+
+            Ok(())
+        }
     }
 }
 
@@ -176,6 +234,11 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Returns true if the schema is registered and has not been deactivated.
+    pub fn is_schema_active(id: &[u8]) -> bool {
+        <Schemas<T>>::contains_key(id) && !Self::schema_deactivated(id)
+    }
+
     pub fn validate_schema_props(props: &Option<Vec<SchemaProperty>>) -> Result<(), Error<T>> {
         if let Some(props) = props {
             ensure!(
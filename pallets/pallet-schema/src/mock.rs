@@ -85,11 +85,12 @@ pub type Timestamp = timestamp::Module<Test>;
 
 pub struct MockOrigin<T>(PhantomData<T>);
 
+// Every signed account acts as its own organization.
 impl<T: Trait> EnsureOrigin<T::Origin> for MockOrigin<T> {
-    type Success = T::AccountId;
+    type Success = (T::AccountId, T::AccountId);
     fn try_origin(o: T::Origin) -> Result<Self::Success, T::Origin> {
         o.into().and_then(|o| match o {
-            RawOrigin::Signed(ref who) => Ok(who.clone()),
+            RawOrigin::Signed(ref who) => Ok((who.clone(), who.clone())),
             r => Err(T::Origin::from(r)),
         })
     }
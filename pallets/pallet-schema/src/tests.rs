@@ -37,7 +37,8 @@ fn create_product_without_props() {
     new_test_ext().execute_with(|| {
         let sender = account_key(TEST_SENDER);
         let id = TEST_SCHEMA_ID.as_bytes().to_owned();
-        let owner = account_key(TEST_ORGANIZATION);
+        // Signed accounts act for themselves in the mock
+        let owner = sender;
         let hash = H256::from_low_u64_be(1);
         let now = 42;
         Timestamp::set_timestamp(now);
@@ -45,7 +46,6 @@ fn create_product_without_props() {
         let result = SchemaRegistry::register_schema(
             Origin::signed(sender),
             id.clone(),
-            owner.clone(),
             hash.clone(),
             None,
         );
@@ -82,7 +82,8 @@ fn create_schema_with_valid_props() {
     new_test_ext().execute_with(|| {
         let sender = account_key(TEST_SENDER);
         let id = TEST_SCHEMA_ID.as_bytes().to_owned();
-        let owner = account_key(TEST_ORGANIZATION);
+        // Signed accounts act for themselves in the mock
+        let owner = sender;
         let hash = H256::from_low_u64_be(1);
         let now = 42;
         Timestamp::set_timestamp(now);
@@ -90,7 +91,6 @@ fn create_schema_with_valid_props() {
         let result = SchemaRegistry::register_schema(
             Origin::signed(sender),
             id.clone(),
-            owner.clone(),
             hash.clone(),
             Some(vec![
                 SchemaProperty::new(
@@ -140,7 +140,6 @@ fn create_schema_with_invalid_sender() {
             SchemaRegistry::register_schema(
                 Origin::none(),
                 vec!(),
-                account_key(TEST_ORGANIZATION),
                 hash.clone(),
                 None
             ),
@@ -157,7 +156,6 @@ fn create_schema_with_missing_id() {
             SchemaRegistry::register_schema(
                 Origin::signed(account_key(TEST_SENDER)),
                 vec!(),
-                account_key(TEST_ORGANIZATION),
                 hash.clone(),
                 None
             ),
@@ -174,7 +172,6 @@ fn create_schema_with_long_id() {
             SchemaRegistry::register_schema(
                 Origin::signed(account_key(TEST_SENDER)),
                 TEST_SCHEMA_DESC.as_bytes().to_owned(),
-                account_key(TEST_ORGANIZATION),
                 hash.clone(),
                 None
             ),
@@ -201,7 +198,6 @@ fn create_schema_with_existing_id() {
             SchemaRegistry::register_schema(
                 Origin::signed(account_key(TEST_SENDER)),
                 existing_schema,
-                account_key(TEST_ORGANIZATION),
                 hash.clone(),
                 None
             ),
@@ -218,7 +214,6 @@ fn create_schema_with_too_many_props() {
             SchemaRegistry::register_schema(
                 Origin::signed(account_key(TEST_SENDER)),
                 TEST_SCHEMA_ID.as_bytes().to_owned(),
-                account_key(TEST_ORGANIZATION),
                 hash.clone(),
                 Some(vec![
                     SchemaProperty::new(b"name1", b"desc1", b"ver1"),
@@ -238,7 +233,6 @@ fn create_schema_with_invalid_name() {
             SchemaRegistry::register_schema(
                 Origin::signed(account_key(TEST_SENDER)),
                 TEST_SCHEMA_ID.as_bytes().to_owned(),
-                account_key(TEST_ORGANIZATION),
                 hash.clone(),
                 Some(vec![
                     SchemaProperty::new(&TEST_SCHEMA_DESC.as_bytes().to_owned(), b"desc1", b"ver1"),
@@ -257,7 +251,6 @@ fn create_schema_with_invalid_desc() {
             SchemaRegistry::register_schema(
                 Origin::signed(account_key(TEST_SENDER)),
                 TEST_SCHEMA_ID.as_bytes().to_owned(),
-                account_key(TEST_ORGANIZATION),
                 hash.clone(),
                 Some(vec![
                     SchemaProperty::new(b"name1", &TEST_SCHEMA_DESC_LONG.as_bytes().to_owned(), b"ver1"),
@@ -277,7 +270,6 @@ fn create_schema_with_invalid_version() {
             SchemaRegistry::register_schema(
                 Origin::signed(account_key(TEST_SENDER)),
                 TEST_SCHEMA_ID.as_bytes().to_owned(),
-                account_key(TEST_ORGANIZATION),
                 hash.clone(),
                 Some(vec![
                     SchemaProperty::new(b"name1", b"desc1", &TEST_SCHEMA_DESC.as_bytes().to_owned()),
@@ -287,3 +279,75 @@ fn create_schema_with_invalid_version() {
         );
     })
 }
+
+#[test]
+fn deactivate_schema_by_owner() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = TEST_SCHEMA_ID.as_bytes().to_owned();
+        let hash = H256::from_low_u64_be(1);
+
+        assert_ok!(SchemaRegistry::register_schema(
+            Origin::signed(owner),
+            id.clone(),
+            hash.clone(),
+            None
+        ));
+        assert!(SchemaRegistry::is_schema_active(&id));
+
+        assert_ok!(SchemaRegistry::deactivate_schema(Origin::signed(owner), id.clone()));
+
+        // Schema is kept but no longer active
+        assert!(SchemaRegistry::schema_by_id(&id).is_some());
+        assert!(!SchemaRegistry::is_schema_active(&id));
+
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::schema_registry(RawEvent::SchemaDeactivated(owner, id.clone()))));
+
+        assert_noop!(
+            SchemaRegistry::deactivate_schema(Origin::signed(owner), id),
+            Error::<Test>::SchemaAlreadyDeactivated
+        );
+    })
+}
+
+#[test]
+fn deactivate_schema_by_non_owner() {
+    new_test_ext().execute_with(|| {
+        let id = TEST_SCHEMA_ID.as_bytes().to_owned();
+        let hash = H256::from_low_u64_be(1);
+
+        store_test_schema::<Test>(id.clone(), account_key(TEST_ORGANIZATION), hash, 42);
+        <OwnerOfSchema<Test>>::insert(&id, account_key(TEST_ORGANIZATION));
+
+        assert_noop!(
+            SchemaRegistry::deactivate_schema(Origin::signed(account_key(TEST_SENDER)), id),
+            Error::<Test>::SchemaNotOwner
+        );
+    })
+}
+
+#[test]
+fn set_schema_transferable_by_owner() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(TEST_ORGANIZATION);
+        let id = TEST_SCHEMA_ID.as_bytes().to_owned();
+
+        store_test_schema::<Test>(id.clone(), owner, H256::from_low_u64_be(1), 42);
+        <OwnerOfSchema<Test>>::insert(&id, owner);
+        assert!(!SchemaRegistry::schema_transferable(&id));
+
+        assert_noop!(
+            SchemaRegistry::set_schema_transferable(Origin::signed(account_key(TEST_SENDER)), id.clone(), true),
+            Error::<Test>::SchemaNotOwner
+        );
+
+        assert_ok!(SchemaRegistry::set_schema_transferable(Origin::signed(owner), id.clone(), true));
+        assert!(SchemaRegistry::schema_transferable(&id));
+        assert!(System::events().iter().any(|er| er.event
+            == TestEvent::schema_registry(RawEvent::SchemaTransferableSet(owner, id.clone(), true))));
+
+        assert_ok!(SchemaRegistry::set_schema_transferable(Origin::signed(owner), id.clone(), false));
+        assert!(!SchemaRegistry::schema_transferable(&id));
+    })
+}
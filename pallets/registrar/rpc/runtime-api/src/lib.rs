@@ -0,0 +1,33 @@
+//! Runtime API definition for the Registrar pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+pub use registrar::{Membership, OrgProfile, OrgRole};
+
+sp_api::decl_runtime_apis! {
+	pub trait RegistrarApi<AccountId, Hash, BlockNumber> where
+		AccountId: Codec,
+		Hash: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the profile of an organization, if one was set.
+		fn organization_profile(org: AccountId) -> Option<OrgProfile<AccountId, Hash>>;
+		/// Returns the organizations sorted by account, `limit` at a time from `offset`.
+		/// Reads the whole organization map on every call.
+		fn organizations(offset: u32, limit: u32) -> Vec<AccountId>;
+		/// Returns the members of an organization.
+		fn organization_members(org: AccountId) -> Vec<AccountId>;
+		/// Returns the organizations an account is a member of.
+		fn organizations_of(account: AccountId) -> Vec<AccountId>;
+		/// Returns the role, expiry and current validity of a membership.
+		fn membership(org: AccountId, account: AccountId) -> Option<Membership<AccountId, BlockNumber>>;
+		/// Returns an organization and all its sub-organizations, with their depth below `root`.
+		fn organization_tree(root: AccountId) -> Vec<(AccountId, u32)>;
+		/// Returns the memberships lapsing within the next `blocks` blocks, as
+		/// (organization, member, expiry) sorted by expiry.
+		fn memberships_expiring_within(blocks: BlockNumber) -> Vec<(AccountId, AccountId, BlockNumber)>;
+	}
+}
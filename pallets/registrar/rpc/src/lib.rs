@@ -0,0 +1,185 @@
+//! RPC interface for the Registrar pallet.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+pub use pallet_registrar_rpc_runtime_api::{
+	Membership, OrgProfile, OrgRole, RegistrarApi as RegistrarRuntimeApi,
+};
+
+#[rpc]
+pub trait RegistrarApi<BlockHash, AccountId, Hash, BlockNumber> {
+	/// Returns the organizations sorted by account, `limit` at a time from `offset`.
+	/// Reads the whole organization map on every call.
+	#[rpc(name = "registrar_organizations")]
+	fn organizations(&self, offset: u32, limit: u32, at: Option<BlockHash>) -> Result<Vec<AccountId>>;
+
+	/// Returns the members of an organization.
+	#[rpc(name = "registrar_organizationMembers")]
+	fn organization_members(&self, org: AccountId, at: Option<BlockHash>) -> Result<Vec<AccountId>>;
+
+	/// Returns the organizations an account is a member of.
+	#[rpc(name = "registrar_organizationsOf")]
+	fn organizations_of(&self, account: AccountId, at: Option<BlockHash>) -> Result<Vec<AccountId>>;
+
+	/// Returns the role, expiry and current validity of a membership.
+	#[rpc(name = "registrar_membership")]
+	fn membership(
+		&self,
+		org: AccountId,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Option<Membership<AccountId, BlockNumber>>>;
+
+	/// Returns the profile of an organization, if one was set.
+	#[rpc(name = "registrar_organizationProfile")]
+	fn organization_profile(
+		&self,
+		org: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Option<OrgProfile<AccountId, Hash>>>;
+
+	/// Returns an organization and all its sub-organizations, with their depth below `root`.
+	#[rpc(name = "registrar_organizationTree")]
+	fn organization_tree(&self, root: AccountId, at: Option<BlockHash>) -> Result<Vec<(AccountId, u32)>>;
+
+	/// Returns the memberships lapsing within the next `blocks` blocks, as
+	/// (organization, member, expiry) sorted by expiry.
+	#[rpc(name = "registrar_membershipsExpiringWithin")]
+	fn memberships_expiring_within(
+		&self,
+		blocks: BlockNumber,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(AccountId, AccountId, BlockNumber)>>;
+}
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i64 = 1;
+
+fn runtime_error(message: &str, e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: message.into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+/// Implements the `RegistrarApi` RPC trait by calling into the runtime.
+pub struct Registrar<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Registrar<C, B> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<C, Block> Registrar<C, Block>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+{
+	fn block_id(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block> {
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
+
+impl<C, Block, AccountId, Hash, BlockNumber>
+	RegistrarApi<<Block as BlockT>::Hash, AccountId, Hash, BlockNumber> for Registrar<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: RegistrarRuntimeApi<Block, AccountId, Hash, BlockNumber>,
+	AccountId: Codec,
+	Hash: Codec,
+	BlockNumber: Codec,
+{
+	fn organizations(
+		&self,
+		offset: u32,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AccountId>> {
+		self.client
+			.runtime_api()
+			.organizations(&self.block_id(at), offset, limit)
+			.map_err(|e| runtime_error("Unable to list organizations.", e))
+	}
+
+	fn organization_members(
+		&self,
+		org: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AccountId>> {
+		self.client
+			.runtime_api()
+			.organization_members(&self.block_id(at), org)
+			.map_err(|e| runtime_error("Unable to list organization members.", e))
+	}
+
+	fn organizations_of(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AccountId>> {
+		self.client
+			.runtime_api()
+			.organizations_of(&self.block_id(at), account)
+			.map_err(|e| runtime_error("Unable to list organizations of account.", e))
+	}
+
+	fn membership(
+		&self,
+		org: AccountId,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Membership<AccountId, BlockNumber>>> {
+		self.client
+			.runtime_api()
+			.membership(&self.block_id(at), org, account)
+			.map_err(|e| runtime_error("Unable to query membership.", e))
+	}
+
+	fn organization_profile(
+		&self,
+		org: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<OrgProfile<AccountId, Hash>>> {
+		self.client
+			.runtime_api()
+			.organization_profile(&self.block_id(at), org)
+			.map_err(|e| runtime_error("Unable to query organization profile.", e))
+	}
+
+	fn organization_tree(
+		&self,
+		root: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(AccountId, u32)>> {
+		self.client
+			.runtime_api()
+			.organization_tree(&self.block_id(at), root)
+			.map_err(|e| runtime_error("Unable to list organization tree.", e))
+	}
+
+	fn memberships_expiring_within(
+		&self,
+		blocks: BlockNumber,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(AccountId, AccountId, BlockNumber)>> {
+		self.client
+			.runtime_api()
+			.memberships_expiring_within(&self.block_id(at), blocks)
+			.map_err(|e| runtime_error("Unable to list expiring memberships.", e))
+	}
+}
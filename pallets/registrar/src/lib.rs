@@ -1,17 +1,147 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use sp_std::{prelude::*, vec::Vec, if_std};
+use sp_std::{prelude::*, vec::Vec};
+use codec::{Decode, Encode};
 use frame_support::{
 	decl_module, decl_event, decl_storage, decl_error,
-	ensure, dispatch,
-	traits::EnsureOrigin
+	debug, ensure, dispatch,
+	storage::migration::take_storage_value,
+	traits::{Currency, EnsureOrigin, Get, ReservableCurrency},
+	weights::Weight,
 };
 use frame_system::{self as system, ensure_signed, RawOrigin};
+use sp_runtime::{traits::{Hash as HashT, Saturating, Zero}, RuntimeDebug};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+// Limits of the organization profile fields, in bytes.
+pub const LEGAL_NAME_MAX_LENGTH: usize = 128;
+pub const REGISTRATION_NUMBER_MAX_LENGTH: usize = 64;
+pub const JURISDICTION_MAX_LENGTH: usize = 16;
+pub const WEBSITE_MAX_LENGTH: usize = 256;
+/// Maximum number of ancestors of a sub-organization.
+pub const MAX_ORGANIZATION_DEPTH: u32 = 4;
+/// Maximum number of organizations returned by a single `organizations_page` query.
+pub const MAX_ORGANIZATIONS_PAGE: u32 = 100;
+/// Maximum number of memberships lapsing at the same block, bounding the work of `on_initialize`.
+pub const MAX_EXPIRIES_PER_BLOCK: usize = 100;
+
+/// Storage layout versions of the pallet, used to run migrations once.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+	/// Organizations kept as a single `Vec<AccountId>`.
+	V1,
+	/// Organizations kept in a map, with a member index per organization.
+	V2,
+	/// Members hold a role instead of the flat `OrgMember` delegate.
+	V3,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1
+	}
+}
+
+/// Role of a member within an organization, stored as a distinct DID delegate type.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum OrgRole {
+	/// Manages the members of the organization.
+	Admin,
+	/// Registers schemas and credentials on behalf of the organization.
+	Issuer,
+	/// Read-only member.
+	Viewer,
+}
+
+impl OrgRole {
+	/// Every role, from the most to the least privileged.
+	pub const ALL: [OrgRole; 3] = [OrgRole::Admin, OrgRole::Issuer, OrgRole::Viewer];
+
+	/// The DID delegate type granted for the role.
+	pub fn delegate_type(&self) -> &'static [u8] {
+		match self {
+			OrgRole::Admin => b"OrgAdmin",
+			OrgRole::Issuer => b"OrgIssuer",
+			OrgRole::Viewer => b"OrgViewer",
+		}
+	}
+}
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
+
+/// A pending request to create an organization.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct Application<Balance, Hash> {
+	/// Name of the organization to create.
+	pub name: Vec<u8>,
+	/// Hash of the off-chain KYC evidence backing the request.
+	pub evidence: Hash,
+	/// Amount reserved from the applicant until the application is decided.
+	pub deposit: Balance,
+}
+
+/// An invitation to join an organization, pending until accepted by the invited account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Invitation<AccountId, BlockNumber> {
+	/// Role granted once the invitation is accepted.
+	pub role: OrgRole,
+	/// Account that issued the invitation.
+	pub inviter: AccountId,
+	/// Number of blocks the membership lasts once accepted, `None` if it doesn't expire.
+	pub duration: Option<BlockNumber>,
+	/// Block from which the invitation can no longer be accepted.
+	pub expires: BlockNumber,
+}
+
+/// Membership of an account in an organization, as reported to clients.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Membership<AccountId, BlockNumber> {
+	pub organization: AccountId,
+	pub member: AccountId,
+	pub role: OrgRole,
+	/// Block at which the membership lapses, `None` if it doesn't expire.
+	pub expires: Option<BlockNumber>,
+	/// Whether the role DID delegate is valid at the current block.
+	pub valid: bool,
+}
+
+/// Structured metadata describing an organization.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct OrgProfile<AccountId, Hash> {
+	/// Registered legal name.
+	pub legal_name: Vec<u8>,
+	/// Company or registration number issued by the registering authority.
+	pub registration_number: Vec<u8>,
+	/// Jurisdiction of registration, e.g. an ISO 3166 code.
+	pub jurisdiction: Vec<u8>,
+	/// Public website URL.
+	pub website: Vec<u8>,
+	/// Hash of the organization logo, stored off-chain.
+	pub logo_hash: Option<Hash>,
+	/// DID to contact the organization.
+	pub contact_did: Option<AccountId>,
+}
 
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Trait: system::Trait + did::Trait {
 	/// Because this pallet emits events, it depends on the runtime's definition of an event.
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+	/// The currency in which application deposits are reserved.
+	type Currency: ReservableCurrency<Self::AccountId>;
+	/// The amount reserved when applying to create an organization.
+	type ApplicationDeposit: Get<BalanceOf<Self>>;
+	/// The origin allowed to approve or reject applications.
+	type ApproveOrigin: EnsureOrigin<Self::Origin>;
+	/// Number of blocks during which an invitation can be accepted.
+	type InvitationPeriod: Get<Self::BlockNumber>;
+	/// Maximum number of blocks a membership lasts, or is extended by when renewed.
+	type MaxMembershipDuration: Get<Self::BlockNumber>;
 }
 
 // Errors inform users why an extrinsic failed.
@@ -23,17 +153,95 @@ decl_error! {
 		InvalidOrganization,
 		/// Cannot add a user to an organization to which they already belong.
 		MemberOfOrganization,
+		/// Cannot remove a user from an organization to which they don't belong.
+		NotMemberOfOrganization,
+		/// The legal name is empty or too long.
+		InvalidLegalName,
+		/// The registration number is too long.
+		InvalidRegistrationNumber,
+		/// The jurisdiction is too long.
+		InvalidJurisdiction,
+		/// The website is too long.
+		InvalidWebsite,
+		/// The organization has no profile to update.
+		ProfileMissing,
+		/// The account already has a pending application.
+		ApplicationExists,
+		/// There is no pending application for the account.
+		ApplicationMissing,
+		/// The applicant can't afford the application deposit.
+		InsufficientDeposit,
+		/// Only the organization or one of its admins can manage members.
+		NotOrganizationAdmin,
+		/// The member already has that role.
+		SameRole,
+		/// The account already has a pending invitation to the organization.
+		InvitationExists,
+		/// There is no invitation for the account.
+		InvitationMissing,
+		/// The invitation can no longer be accepted.
+		InvitationExpired,
+		/// A membership duration must be at least one block and at most `MaxMembershipDuration`.
+		InvalidDuration,
+		/// Too many memberships already lapse at that block.
+		ExpiryBlockFull,
+		/// The organization has no parent organization.
+		NotSubOrganization,
+		/// The organization still has sub-organizations.
+		HasSubOrganizations,
+		/// The organization tree can't get any deeper.
+		MaxDepthReached,
+		/// The membership doesn't expire and can't be renewed.
+		MembershipNotExpiring,
+		/// No sub-organization account can be derived from the parent and name.
+		InvalidSubOrganizationId,
 	}
 }
 
 // Pallets use events to inform users when important changes are made.
 // https://substrate.dev/docs/en/knowledgebase/runtime/events
 decl_event!(
-	pub enum Event<T> where AccountId = <T as system::Trait>::AccountId {
+	pub enum Event<T> where
+		AccountId = <T as system::Trait>::AccountId,
+		Hash = <T as system::Trait>::Hash,
+		BlockNumber = <T as system::Trait>::BlockNumber,
+	{
+		/// An account applied to create an organization. [applicant, organization_id, evidence]
+		AppliedForOrganization(AccountId, Vec<u8>, Hash),
+		/// An application was rejected and its deposit slashed. [applicant]
+		RejectedOrganization(AccountId),
+		/// An applicant withdrew their application. [applicant]
+		WithdrewApplication(AccountId),
 		/// An organization has been created. [creator, organization_id]
 		CreatedOrganization(AccountId, Vec<u8>),
-		/// An account was added to an organization. [account, organization_id]
-		AddedToOrganization(AccountId, Vec<u8>),
+		/// An account was added to an organization. [organization, account, role]
+		AddedToOrganization(AccountId, AccountId, OrgRole),
+		/// An account was invited to an organization. [organization, account, role, expires]
+		MemberInvited(AccountId, AccountId, OrgRole, BlockNumber),
+		/// A pending invitation was cancelled. [organization, account]
+		InvitationCancelled(AccountId, AccountId),
+		/// A member left an organization. [organization, account]
+		LeftOrganization(AccountId, AccountId),
+		/// A membership was renewed. [organization, account, expires]
+		MembershipRenewed(AccountId, AccountId, BlockNumber),
+		/// A membership lapsed at the end of its duration. [organization, account]
+		MembershipExpired(AccountId, AccountId),
+		/// A sub-organization was created. [parent, sub_organization, organization_id]
+		CreatedSubOrganization(AccountId, AccountId, Vec<u8>),
+		/// The roles members of the parent hold in a sub-organization were changed. [sub_organization, roles]
+		InheritedRolesSet(AccountId, Vec<OrgRole>),
+		/// A member selected the organization it acts for. [account, organization]
+		ActingOrganizationSet(AccountId, Option<AccountId>),
+		/// The role of a member was changed. [organization, account, role]
+		MemberRoleChanged(AccountId, AccountId, OrgRole),
+		/// An account was removed from an organization. [organization, account]
+		RemovedFromOrganization(AccountId, AccountId),
+		/// An organization was dissolved and its members removed. [organization]
+		DissolvedOrganization(AccountId),
+		/// The profile of an organization was set or changed. [organization]
+		OrganizationProfileUpdated(AccountId),
+		/// The logo of an organization was changed. [organization, logo_hash]
+		OrganizationLogoUpdated(AccountId, Option<Hash>),
 	}
 );
 
@@ -41,11 +249,35 @@ decl_event!(
 // https://substrate.dev/docs/en/knowledgebase/runtime/storage
 decl_storage! {
 	trait Store for Module<T: Trait> as registrar {
-			/// The list of organizations in the supply chain consortium.
+			/// The organizations in the supply chain consortium.
 			/// Organizations are identified by the ID of the account that created them.
-			pub Organizations get(fn organizations): Vec<T::AccountId>;
-			/// Maps organizations to their members.
+			pub Organizations get(fn is_organization): map hasher(blake2_128_concat) T::AccountId => bool;
+			/// Maps accounts to the organizations they are a member of.
 			pub OrganizationsOf get(fn organizations_of):map hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
+			/// Maps organizations to their members.
+			pub MembersOf get(fn members_of): map hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
+			/// Role of each member within an organization.
+			pub RoleOf get(fn role_of): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => Option<OrgRole>;
+			/// Pending invitations, by organization and invited account.
+			pub Invitations get(fn invitation): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => Option<Invitation<T::AccountId, T::BlockNumber>>;
+			/// Block at which a time-limited membership lapses, by organization and member.
+			pub MembershipExpiry get(fn membership_expiry): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+			/// Memberships lapsing at a block, as (organization, member) pairs.
+			pub ExpiringMemberships get(fn expiring_memberships): map hasher(twox_64_concat) T::BlockNumber => Vec<(T::AccountId, T::AccountId)>;
+			/// Parent of each sub-organization.
+			pub ParentOf get(fn parent_of): map hasher(blake2_128_concat) T::AccountId => Option<T::AccountId>;
+			/// Direct sub-organizations of each organization.
+			pub SubOrganizations get(fn sub_organizations): map hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
+			/// Roles members of the parent also hold in a sub-organization.
+			pub InheritedRoles get(fn inherited_roles): map hasher(blake2_128_concat) T::AccountId => Vec<OrgRole>;
+			/// Organization a member of several organizations acts for.
+			pub ActingOrganization get(fn acting_organization): map hasher(blake2_128_concat) T::AccountId => Option<T::AccountId>;
+			/// Structured metadata of organizations.
+			pub Profiles get(fn organization_profile): map hasher(blake2_128_concat) T::AccountId => Option<OrgProfile<T::AccountId, T::Hash>>;
+			/// Pending applications to create an organization, by applicant.
+			pub Applications get(fn application): map hasher(blake2_128_concat) T::AccountId => Option<Application<BalanceOf<T>, T::Hash>>;
+			/// Storage layout version, see `Releases`. New chains start at the latest release.
+			StorageVersion get(fn storage_version) build(|_| Releases::V3): Releases;
 	}
 }
 
@@ -57,85 +289,799 @@ decl_module! {
 		fn deposit_event() = default;
 		type Error = Error<T>;
 
-		/// Create an organization. Will return an OrganizationExists error if the organization has already
-		/// been created. Will emit a CreatedOrganization event on success.
+		/// The amount reserved when applying to create an organization.
+		const ApplicationDeposit: BalanceOf<T> = T::ApplicationDeposit::get();
+		/// Number of blocks during which an invitation can be accepted.
+		const InvitationPeriod: T::BlockNumber = T::InvitationPeriod::get();
+		/// Maximum number of blocks a membership lasts, or is extended by when renewed.
+		const MaxMembershipDuration: T::BlockNumber = T::MaxMembershipDuration::get();
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let expired = <ExpiringMemberships<T>>::take(now);
+			let mut writes: Weight = 1;
+			for (org, account) in expired.iter() {
+				// Renewed memberships were moved to a later block.
+				if Self::membership_expiry(org, account) == Some(now) {
+					let _ = Self::remove_member(org, account);
+					Self::deposit_event(RawEvent::MembershipExpired(org.clone(), account.clone()));
+					writes += 5;
+				}
+			}
+			T::DbWeight::get().reads_writes(1 + 2 * expired.len() as Weight, writes)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = 0;
+			if Self::storage_version() == Releases::V1 {
+				weight += Self::migrate_to_v2();
+			}
+			if Self::storage_version() == Releases::V2 {
+				weight += Self::migrate_to_v3();
+			}
+			weight
+		}
+
+		/// Apply to create an organization, backed by the hash of the KYC evidence. Reserves the
+		/// application deposit until the application is approved, rejected or withdrawn.
+		/// Will return an OrganizationExists error if the organization has already been created.
+		/// Will emit an AppliedForOrganization event on success.
 		///
 		/// The dispatch origin for this call must be Signed.
 		#[weight = 10_000]
-		pub fn create_organization(origin, org_name: Vec<u8>) -> dispatch::DispatchResult {
+		pub fn apply_for_organization(origin, org_name: Vec<u8>, evidence: T::Hash) -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
-			let mut orgs = Self::organizations();
-			ensure!(!orgs.contains(&who), Error::<T>::OrganizationExists);
+			ensure!(!Self::is_organization(&who), Error::<T>::OrganizationExists);
+			ensure!(!<Applications<T>>::contains_key(&who), Error::<T>::ApplicationExists);
 
-			orgs.push(who.clone());
-			<Organizations<T>>::put(orgs);
+			let deposit = T::ApplicationDeposit::get();
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
 
-			// DID add attribute
-			<did::Module<T>>::create_attribute(who.clone(), &who, b"Org", &org_name, None)?;
+			let application = Application { name: org_name.clone(), evidence, deposit };
+			<Applications<T>>::insert(&who, application);
 
-			Self::deposit_event(RawEvent::CreatedOrganization(who, org_name));
+			Self::deposit_event(RawEvent::AppliedForOrganization(who, org_name, evidence));
 			Ok(())
 		}
 
-		/// Add an account to an organization. Will return an InvalidOrganization error if the organization
-		/// does not exist or the account is already a member. Will emit a AddedToOrganization event on success.
+		/// Approve a pending application, creating the organization and returning the deposit.
+		/// Will emit a CreatedOrganization event on success.
 		///
-		/// The dispatch origin for this call must be Signed.
+		/// The dispatch origin for this call must be `ApproveOrigin`.
 		#[weight = 10_000]
-		pub fn add_to_organization(origin, account: T::AccountId) -> dispatch::DispatchResult {
+		pub fn approve_organization(origin, applicant: T::AccountId) -> dispatch::DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			let application = Self::application(&applicant).ok_or(Error::<T>::ApplicationMissing)?;
+
+			Self::set_org_attribute(&applicant, &application.name)?;
+
+			<Applications<T>>::remove(&applicant);
+			T::Currency::unreserve(&applicant, application.deposit);
+			<Organizations<T>>::insert(&applicant, true);
+
+			Self::deposit_event(RawEvent::CreatedOrganization(applicant, application.name));
+			Ok(())
+		}
+
+		/// Reject a pending application. The deposit is slashed.
+		/// Will emit a RejectedOrganization event on success.
+		///
+		/// The dispatch origin for this call must be `ApproveOrigin`.
+		#[weight = 10_000]
+		pub fn reject_organization(origin, applicant: T::AccountId) -> dispatch::DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			let application = <Applications<T>>::take(&applicant).ok_or(Error::<T>::ApplicationMissing)?;
+
+			let _ = T::Currency::slash_reserved(&applicant, application.deposit);
+
+			Self::deposit_event(RawEvent::RejectedOrganization(applicant));
+			Ok(())
+		}
+
+		/// Withdraw a pending application and get the deposit back.
+		/// Will emit a WithdrewApplication event on success.
+		///
+		/// The dispatch origin for this call must be Signed by the applicant.
+		#[weight = 10_000]
+		pub fn withdraw_application(origin) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			let application = <Applications<T>>::take(&who).ok_or(Error::<T>::ApplicationMissing)?;
+
+			T::Currency::unreserve(&who, application.deposit);
+
+			Self::deposit_event(RawEvent::WithdrewApplication(who));
+			Ok(())
+		}
+
+		/// Invite an account to join an organization with the given role, for `duration` blocks once
+		/// accepted or without limit. The invitation expires after `InvitationPeriod` blocks. Will return
+		/// a MemberOfOrganization error if the account is already a member or an InvitationExists error if
+		/// it has a pending invitation. Will emit a MemberInvited event on success.
+		///
+		/// The dispatch origin for this call must be Signed by the organization or one of its admins.
+		#[weight = 10_000]
+		pub fn invite_to_organization(
+			origin,
+			org: T::AccountId,
+			account: T::AccountId,
+			role: OrgRole,
+			duration: Option<T::BlockNumber>,
+		) -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
-			// Organizations list.
-			let orgs = Self::organizations();
-			ensure!(orgs.contains(&who), Error::<T>::InvalidOrganization);
+			Self::ensure_admin(&org, &who)?;
+			ensure!(!Self::organizations_of(&account).contains(&org), Error::<T>::MemberOfOrganization);
+			ensure!(duration.map_or(true, |blocks| Self::valid_duration(blocks)), Error::<T>::InvalidDuration);
+
+			let now = <system::Module<T>>::block_number();
+			if let Some(invitation) = Self::invitation(&org, &account) {
+				ensure!(invitation.expires <= now, Error::<T>::InvitationExists);
+			}
+
+			let expires = now + T::InvitationPeriod::get();
+			<Invitations<T>>::insert(&org, &account, Invitation { role, inviter: who, duration, expires });
+
+			Self::deposit_event(RawEvent::MemberInvited(org, account, role, expires));
+			Ok(())
+		}
+
+		/// Cancel a pending invitation. Will emit an InvitationCancelled event on success.
+		///
+		/// The dispatch origin for this call must be Signed by the organization or one of its admins.
+		#[weight = 10_000]
+		pub fn cancel_invitation(origin, org: T::AccountId, account: T::AccountId) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_admin(&org, &who)?;
+			ensure!(<Invitations<T>>::contains_key(&org, &account), Error::<T>::InvitationMissing);
+
+			<Invitations<T>>::remove(&org, &account);
+
+			Self::deposit_event(RawEvent::InvitationCancelled(org, account));
+			Ok(())
+		}
+
+		/// Accept an invitation and join the organization with the invited role. Will return an
+		/// InvitationExpired error if the invitation has expired, or an ExpiryBlockFull error if too
+		/// many memberships lapse at the same block. Will emit an AddedToOrganization event on success.
+		///
+		/// The dispatch origin for this call must be Signed by the invited account.
+		#[weight = 10_000]
+		pub fn accept_invitation(origin, org: T::AccountId) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			let invitation = Self::invitation(&org, &who).ok_or(Error::<T>::InvitationMissing)?;
+			let now = <system::Module<T>>::block_number();
+			ensure!(invitation.expires > now, Error::<T>::InvitationExpired);
+			ensure!(Self::is_organization(&org), Error::<T>::InvalidOrganization);
 
 			// Accounts that belong to a certain organization.
-			let mut orgs = Self::organizations_of(&account);
-
-			// Validate organization and account should not be part.
-			if !orgs.contains(&who) {
-				orgs.push(who.clone());
-				OrganizationsOf::<T>::insert(&account, orgs);
-			} else {
-				return Err(Error::<T>::MemberOfOrganization.into());
+			let mut orgs = Self::organizations_of(&who);
+			ensure!(!orgs.contains(&org), Error::<T>::MemberOfOrganization);
+
+			// Add account as a DID delegate of its role.
+			let valid_until = invitation.duration.map(|blocks| now.saturating_add(blocks));
+			if let Some(expiry) = valid_until {
+				ensure!(Self::expiring_memberships(expiry).len() < MAX_EXPIRIES_PER_BLOCK, Error::<T>::ExpiryBlockFull);
+			}
+			Self::grant_role(&org, &who, invitation.role, valid_until)?;
+			if let Some(expiry) = valid_until {
+				<MembershipExpiry<T>>::insert(&org, &who, expiry);
+				<ExpiringMemberships<T>>::append(expiry, (&org, &who));
+			}
+			orgs.push(org.clone());
+			<OrganizationsOf<T>>::insert(&who, orgs);
+			<MembersOf<T>>::append(&org, &who);
+			<Invitations<T>>::remove(&org, &who);
+
+			Self::deposit_event(RawEvent::AddedToOrganization(org, who, invitation.role));
+			Ok(())
+		}
+
+		/// Select the organization the caller acts for when dispatching through `EnsureOrg` or
+		/// `EnsureOrgIssuer`, or clear the selection to act for the first organization granting the
+		/// required role. Will emit an ActingOrganizationSet event on success.
+		///
+		/// The dispatch origin for this call must be Signed by a member of the organization.
+		#[weight = 10_000]
+		pub fn set_acting_organization(origin, org: Option<T::AccountId>) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			match org {
+				Some(ref org) => {
+					ensure!(Self::holds_any_role(org, &who), Error::<T>::NotMemberOfOrganization);
+					<ActingOrganization<T>>::insert(&who, org);
+				}
+				None => <ActingOrganization<T>>::remove(&who),
 			}
 
-			// Add account as a DID delegate.
-			<did::Module<T>>::create_delegate(&who, &who, &account, &b"OrgMember".to_vec(), None)?;
+			Self::deposit_event(RawEvent::ActingOrganizationSet(who, org));
+			Ok(())
+		}
+
+		/// Leave an organization, revoking the role DID delegate. Will return a NotMemberOfOrganization
+		/// error if the account is not a member. Will emit a LeftOrganization event on success.
+		///
+		/// The dispatch origin for this call must be Signed by the member.
+		#[weight = 10_000]
+		pub fn leave_organization(origin, org: T::AccountId) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::organizations_of(&who).contains(&org), Error::<T>::NotMemberOfOrganization);
+
+			Self::remove_member(&org, &who)?;
+
+			Self::deposit_event(RawEvent::LeftOrganization(org, who));
+			Ok(())
+		}
+
+		/// Change the role of a member, replacing its DID delegate. Will return a NotMemberOfOrganization
+		/// error if the account is not a member. Will emit a MemberRoleChanged event on success.
+		///
+		/// The dispatch origin for this call must be Signed by the organization or one of its admins.
+		#[weight = 10_000]
+		pub fn set_member_role(
+			origin,
+			org: T::AccountId,
+			account: T::AccountId,
+			role: OrgRole,
+		) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_admin(&org, &who)?;
+			let current = Self::role_of(&org, &account).ok_or(Error::<T>::NotMemberOfOrganization)?;
+			ensure!(current != role, Error::<T>::SameRole);
+
+			Self::revoke_role(&org, &account, current)?;
+			Self::grant_role(&org, &account, role, Self::membership_expiry(&org, &account))?;
+
+			Self::deposit_event(RawEvent::MemberRoleChanged(org, account, role));
+			Ok(())
+		}
+
+		/// Extend a time-limited membership by `duration` blocks. Will return a MembershipNotExpiring
+		/// error if the membership doesn't expire, or an ExpiryBlockFull error if too many memberships
+		/// lapse at the renewed block. Will emit a MembershipRenewed event on success.
+		///
+		/// The dispatch origin for this call must be Signed by the organization or one of its admins.
+		#[weight = 10_000]
+		pub fn renew_membership(
+			origin,
+			org: T::AccountId,
+			account: T::AccountId,
+			duration: T::BlockNumber,
+		) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_admin(&org, &who)?;
+			let role = Self::role_of(&org, &account).ok_or(Error::<T>::NotMemberOfOrganization)?;
+			let expiry = Self::membership_expiry(&org, &account).ok_or(Error::<T>::MembershipNotExpiring)?;
+			ensure!(Self::valid_duration(duration), Error::<T>::InvalidDuration);
+
+			let renewed = expiry.saturating_add(duration);
+			ensure!(Self::expiring_memberships(renewed).len() < MAX_EXPIRIES_PER_BLOCK, Error::<T>::ExpiryBlockFull);
+			let owner = <did::Module<T>>::identity_owner(&org);
+			<did::Module<T>>::renew_delegate(&owner, &org, &account, role.delegate_type(), renewed)?;
+
+			Self::unschedule_expiry(&org, &account, expiry);
+			<MembershipExpiry<T>>::insert(&org, &account, renewed);
+			<ExpiringMemberships<T>>::append(renewed, (&org, &account));
+
+			Self::deposit_event(RawEvent::MembershipRenewed(org, account, renewed));
+			Ok(())
+		}
+
+		/// Remove an account from an organization, revoking its role DID delegate. Will return a
+		/// NotMemberOfOrganization error if the account is not a member. Will emit a RemovedFromOrganization
+		/// event on success.
+		///
+		/// The dispatch origin for this call must be Signed by the organization or one of its admins.
+		#[weight = 10_000]
+		pub fn remove_from_organization(origin, org: T::AccountId, account: T::AccountId) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_admin(&org, &who)?;
+			ensure!(
+				Self::organizations_of(&account).contains(&org),
+				Error::<T>::NotMemberOfOrganization
+			);
+
+			Self::remove_member(&org, &account)?;
+
+			Self::deposit_event(RawEvent::RemovedFromOrganization(org, account));
+			Ok(())
+		}
+
+		/// Dissolve an organization: every member is removed and the `Org` DID attribute revoked.
+		/// Will return a HasSubOrganizations error if sub-organizations remain. Will emit a
+		/// DissolvedOrganization event on success.
+		///
+		/// The dispatch origin for this call must be Signed by the organization.
+		#[weight = 100_000]
+		pub fn dissolve_organization(origin) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_organization(&who), Error::<T>::InvalidOrganization);
+
+			Self::dissolve(&who, who.clone())?;
+
+			Self::deposit_event(RawEvent::DissolvedOrganization(who));
+			Ok(())
+		}
+
+		/// Create a sub-organization (subsidiary, department...) of `parent`. Its account is derived
+		/// from the parent and the `org_name`, and its DID is owned by the parent. Members of the parent
+		/// also hold the `inherited_roles` in the sub-organization. Will emit a CreatedSubOrganization
+		/// event on success.
+		///
+		/// The dispatch origin for this call must be Signed by the parent organization or one of its admins.
+		#[weight = 10_000]
+		pub fn create_sub_organization(
+			origin,
+			parent: T::AccountId,
+			org_name: Vec<u8>,
+			inherited_roles: Vec<OrgRole>,
+		) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_admin(&parent, &who)?;
+			ensure!(Self::depth_of(&parent) < MAX_ORGANIZATION_DEPTH, Error::<T>::MaxDepthReached);
+
+			let sub = Self::sub_organization_id(&parent, &org_name)?;
+			ensure!(!Self::is_organization(&sub), Error::<T>::OrganizationExists);
+
+			// DID add attribute, then hand the DID over to the parent. The DID of a dissolved
+			// sub-organization still belongs to the parent.
+			Self::set_org_attribute(&sub, &org_name)?;
+			<did::Module<T>>::set_owner(&sub, &parent);
+
+			<Organizations<T>>::insert(&sub, true);
+			<ParentOf<T>>::insert(&sub, &parent);
+			<SubOrganizations<T>>::append(&parent, &sub);
+			<InheritedRoles<T>>::insert(&sub, Self::unique_roles(inherited_roles));
 
-			Self::deposit_event(RawEvent::AddedToOrganization(who, b"OrgMember".to_vec()));
+			Self::deposit_event(RawEvent::CreatedSubOrganization(parent, sub, org_name));
+			Ok(())
+		}
+
+		/// Change the roles members of the parent hold in a sub-organization. Will emit an
+		/// InheritedRolesSet event on success.
+		///
+		/// The dispatch origin for this call must be Signed by the parent organization or one of its admins.
+		#[weight = 10_000]
+		pub fn set_inherited_roles(origin, sub: T::AccountId, inherited_roles: Vec<OrgRole>) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			let parent = Self::parent_of(&sub).ok_or(Error::<T>::NotSubOrganization)?;
+			Self::ensure_admin(&parent, &who)?;
+
+			let roles = Self::unique_roles(inherited_roles);
+			<InheritedRoles<T>>::insert(&sub, &roles);
+
+			Self::deposit_event(RawEvent::InheritedRolesSet(sub, roles));
+			Ok(())
+		}
+
+		/// Dissolve a sub-organization, see `dissolve_organization`.
+		///
+		/// The dispatch origin for this call must be Signed by the parent organization or one of its admins.
+		#[weight = 100_000]
+		pub fn dissolve_sub_organization(origin, sub: T::AccountId) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			let parent = Self::parent_of(&sub).ok_or(Error::<T>::NotSubOrganization)?;
+			Self::ensure_admin(&parent, &who)?;
+
+			Self::dissolve(&sub, <did::Module<T>>::identity_owner(&sub))?;
+			<ParentOf<T>>::remove(&sub);
+			<InheritedRoles<T>>::remove(&sub);
+			<SubOrganizations<T>>::mutate(&parent, |subs| subs.retain(|s| *s != sub));
+
+			Self::deposit_event(RawEvent::DissolvedOrganization(sub));
+			Ok(())
+		}
+
+		/// Set the profile of an organization, replacing any previous one. Will emit an
+		/// OrganizationProfileUpdated event on success.
+		///
+		/// The dispatch origin for this call must be Signed by the organization.
+		#[weight = 10_000]
+		pub fn set_organization_profile(
+			origin,
+			legal_name: Vec<u8>,
+			registration_number: Vec<u8>,
+			jurisdiction: Vec<u8>,
+			website: Vec<u8>,
+			logo_hash: Option<T::Hash>,
+			contact_did: Option<T::AccountId>,
+		) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_organization(&who), Error::<T>::InvalidOrganization);
+
+			let profile = OrgProfile {
+				legal_name,
+				registration_number,
+				jurisdiction,
+				website,
+				logo_hash,
+				contact_did,
+			};
+			Self::validate_profile(&profile)?;
+			<Profiles<T>>::insert(&who, profile);
+
+			Self::deposit_event(RawEvent::OrganizationProfileUpdated(who));
+			Ok(())
+		}
+
+		/// Change the website and contact DID of an organization profile. Will return a
+		/// ProfileMissing error if no profile was set. Will emit an OrganizationProfileUpdated event on success.
+		///
+		/// The dispatch origin for this call must be Signed by the organization.
+		#[weight = 10_000]
+		pub fn update_organization_contact(
+			origin,
+			website: Vec<u8>,
+			contact_did: Option<T::AccountId>,
+		) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut profile = Self::organization_profile(&who).ok_or(Error::<T>::ProfileMissing)?;
+
+			profile.website = website;
+			profile.contact_did = contact_did;
+			Self::validate_profile(&profile)?;
+			<Profiles<T>>::insert(&who, profile);
+
+			Self::deposit_event(RawEvent::OrganizationProfileUpdated(who));
+			Ok(())
+		}
+
+		/// Change the logo hash of an organization profile. Will return a ProfileMissing error if
+		/// no profile was set. Will emit an OrganizationLogoUpdated event on success.
+		///
+		/// The dispatch origin for this call must be Signed by the organization.
+		#[weight = 10_000]
+		pub fn update_organization_logo(origin, logo_hash: Option<T::Hash>) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut profile = Self::organization_profile(&who).ok_or(Error::<T>::ProfileMissing)?;
+
+			profile.logo_hash = logo_hash;
+			<Profiles<T>>::insert(&who, profile);
+
+			Self::deposit_event(RawEvent::OrganizationLogoUpdated(who, logo_hash));
 			Ok(())
 		}
 	}
 }
 
 impl<T: Trait> Module<T> {
-	/// Returns true if and only if the account is a member of an organization.
+	/// Checks the profile fields are within their size limits.
+	pub fn validate_profile(profile: &OrgProfile<T::AccountId, T::Hash>) -> dispatch::DispatchResult {
+		ensure!(
+			!profile.legal_name.is_empty() && profile.legal_name.len() <= LEGAL_NAME_MAX_LENGTH,
+			Error::<T>::InvalidLegalName
+		);
+		ensure!(
+			profile.registration_number.len() <= REGISTRATION_NUMBER_MAX_LENGTH,
+			Error::<T>::InvalidRegistrationNumber
+		);
+		ensure!(profile.jurisdiction.len() <= JURISDICTION_MAX_LENGTH, Error::<T>::InvalidJurisdiction);
+		ensure!(profile.website.len() <= WEBSITE_MAX_LENGTH, Error::<T>::InvalidWebsite);
+		Ok(())
+	}
+
+	/// Ensures the organization exists and `who` is the organization or one of its admins.
+	pub fn ensure_admin(org: &T::AccountId, who: &T::AccountId) -> dispatch::DispatchResult {
+		ensure!(Self::is_organization(org), Error::<T>::InvalidOrganization);
+		ensure!(who == org || Self::has_role(org, who, OrgRole::Admin), Error::<T>::NotOrganizationAdmin);
+		Ok(())
+	}
+
+	/// Returns true if the organization exists and the account holds a valid DID delegate for the
+	/// role in it, or holds the role in the parent of a sub-organization inheriting it.
+	/// The DID owner holds every delegate type, so the organization must be checked first.
+	pub fn has_role(org: &T::AccountId, account: &T::AccountId, role: OrgRole) -> bool {
+		if !Self::is_organization(org) {
+			return false;
+		}
+		<did::Module<T>>::valid_delegate(org, role.delegate_type(), account).is_ok()
+			|| (Self::inherited_roles(org).contains(&role)
+				&& Self::parent_of(org).map_or(false, |parent| Self::has_role(&parent, account, role)))
+	}
+
+	/// Returns true if the account holds any role in the organization, directly or inherited.
+	pub fn holds_any_role(org: &T::AccountId, account: &T::AccountId) -> bool {
+		OrgRole::ALL.iter().any(|role| Self::has_role(org, account, *role))
+	}
+
+	/// Account of the sub-organization named `org_name` of `parent`. Nobody holds its key.
+	pub fn sub_organization_id(parent: &T::AccountId, org_name: &[u8]) -> Result<T::AccountId, Error<T>> {
+		let entropy = T::Hashing::hash_of(&(b"registrar/suborg", parent, org_name));
+		T::AccountId::decode(&mut entropy.as_ref()).map_err(|_| Error::<T>::InvalidSubOrganizationId)
+	}
+
+	/// Organizations sorted by account, skipping the first `offset` ones and returning at most
+	/// `limit` (capped to `MAX_ORGANIZATIONS_PAGE`).
+	///
+	/// Every call reads and sorts the whole `Organizations` map, as it is keyed by hash. It is
+	/// only meant for off-chain queries through the runtime API, never from a dispatchable.
+	pub fn organizations_page(offset: u32, limit: u32) -> Vec<T::AccountId> {
+		let mut orgs: Vec<T::AccountId> = <Organizations<T>>::iter().map(|(org, _)| org).collect();
+		orgs.sort();
+		orgs.into_iter()
+			.skip(offset as usize)
+			.take(limit.min(MAX_ORGANIZATIONS_PAGE) as usize)
+			.collect()
+	}
+
+	/// The membership of an account in an organization, if it was added as a member.
+	pub fn membership(org: &T::AccountId, account: &T::AccountId) -> Option<Membership<T::AccountId, T::BlockNumber>> {
+		Self::role_of(org, account).map(|role| Membership {
+			organization: org.clone(),
+			member: account.clone(),
+			role,
+			expires: Self::membership_expiry(org, account),
+			valid: Self::has_role(org, account, role),
+		})
+	}
+
+	/// Number of ancestors of an organization.
+	pub fn depth_of(org: &T::AccountId) -> u32 {
+		let mut depth = 0;
+		let mut current = Self::parent_of(org);
+		while let Some(parent) = current {
+			depth += 1;
+			current = Self::parent_of(&parent);
+		}
+		depth
+	}
+
+	/// The organization and all its descendants in depth-first order, as (organization, depth
+	/// relative to `root`) pairs. Empty if `root` is not an organization.
+	pub fn organization_tree(root: &T::AccountId) -> Vec<(T::AccountId, u32)> {
+		let mut tree = Vec::new();
+		if !Self::is_organization(root) {
+			return tree;
+		}
+		let mut stack = Vec::new();
+		stack.push((root.clone(), 0));
+		while let Some((org, depth)) = stack.pop() {
+			// Push children in reverse so they are visited in creation order.
+			for sub in Self::sub_organizations(&org).into_iter().rev() {
+				stack.push((sub, depth + 1));
+			}
+			tree.push((org, depth));
+		}
+		tree
+	}
+
+	fn valid_duration(blocks: T::BlockNumber) -> bool {
+		!blocks.is_zero() && blocks <= T::MaxMembershipDuration::get()
+	}
+
+	fn unique_roles(roles: Vec<OrgRole>) -> Vec<OrgRole> {
+		OrgRole::ALL.iter().copied().filter(|role| roles.contains(role)).collect()
+	}
+
+	// Adds the `Org` DID attribute on behalf of the DID owner. The attribute of a dissolved
+	// organization is renewed, as creating it again would collide with the revoked one.
+	fn set_org_attribute(org: &T::AccountId, org_name: &[u8]) -> dispatch::DispatchResult {
+		let owner = <did::Module<T>>::identity_owner(org);
+		if <did::Module<T>>::attribute_and_id(org, b"Org").is_some() {
+			<did::Module<T>>::renew_attribute(owner, org, b"Org", org_name, None)
+		} else {
+			<did::Module<T>>::create_attribute(owner, org, b"Org", org_name, None)
+		}
+	}
+
+	// Removes every member and the `Org` DID attribute, then the organization itself.
+	fn dissolve(org: &T::AccountId, did_owner: T::AccountId) -> dispatch::DispatchResult {
+		ensure!(Self::sub_organizations(org).is_empty(), Error::<T>::HasSubOrganizations);
+
+		// Revoke the organization attribute first, it fails if the DID is no longer owned.
+		<did::Module<T>>::reset_attribute(did_owner, org, b"Org")?;
+
+		for account in Self::members_of(org) {
+			Self::remove_member(org, &account)?;
+		}
+		<MembersOf<T>>::remove(org);
+		<Invitations<T>>::remove_prefix(org);
+		<Profiles<T>>::remove(org);
+		<Organizations<T>>::remove(org);
+		Ok(())
+	}
+
+	// Grants the role delegate on behalf of the organization's DID owner.
+	// The delegate remains valid until `valid_until` if set.
+	fn grant_role(
+		org: &T::AccountId,
+		account: &T::AccountId,
+		role: OrgRole,
+		valid_until: Option<T::BlockNumber>,
+	) -> dispatch::DispatchResult {
+		let owner = <did::Module<T>>::identity_owner(org);
+		let valid_for = valid_until.map(|block| block.saturating_sub(<system::Module<T>>::block_number()));
+		<did::Module<T>>::create_delegate(&owner, org, account, &role.delegate_type().to_vec(), valid_for)?;
+		<RoleOf<T>>::insert(org, account, role);
+		Ok(())
+	}
+
+	// Expires the role delegate. It may already have been revoked directly through the DID pallet.
+	fn revoke_role(org: &T::AccountId, account: &T::AccountId, role: OrgRole) -> dispatch::DispatchResult {
+		if <did::Module<T>>::valid_listed_delegate(org, role.delegate_type(), account).is_ok() {
+			let owner = <did::Module<T>>::identity_owner(org);
+			<did::Module<T>>::expire_delegate(&owner, org, account, role.delegate_type())?;
+		}
+		<RoleOf<T>>::remove(org, account);
+		Ok(())
+	}
+
+	// Revokes the membership of an account and removes it from the membership indexes.
+	fn remove_member(org: &T::AccountId, account: &T::AccountId) -> dispatch::DispatchResult {
+		if let Some(role) = Self::role_of(org, account) {
+			Self::revoke_role(org, account, role)?;
+		}
+		if let Some(expiry) = <MembershipExpiry<T>>::take(org, account) {
+			Self::unschedule_expiry(org, account, expiry);
+		}
+
+		<OrganizationsOf<T>>::mutate_exists(account, |orgs| {
+			if let Some(list) = orgs {
+				list.retain(|o| o != org);
+				if list.is_empty() {
+					*orgs = None;
+				}
+			}
+		});
+		<MembersOf<T>>::mutate(org, |members| members.retain(|member| member != account));
+		if Self::acting_organization(account).as_ref() == Some(org) {
+			<ActingOrganization<T>>::remove(account);
+		}
+		Ok(())
+	}
+
+	// Drops a membership from the list of those lapsing at `expiry`.
+	fn unschedule_expiry(org: &T::AccountId, account: &T::AccountId, expiry: T::BlockNumber) {
+		<ExpiringMemberships<T>>::mutate_exists(expiry, |memberships| {
+			if let Some(list) = memberships {
+				list.retain(|(o, a)| o != org || a != account);
+				if list.is_empty() {
+					*memberships = None;
+				}
+			}
+		});
+	}
+
+	/// Memberships lapsing within the next `blocks` blocks, as (organization, member, expiry)
+	/// sorted by expiry.
+	pub fn memberships_expiring_within(blocks: T::BlockNumber) -> Vec<(T::AccountId, T::AccountId, T::BlockNumber)> {
+		let now = <system::Module<T>>::block_number();
+		let until = now.saturating_add(blocks);
+		let mut expiring: Vec<_> = <ExpiringMemberships<T>>::iter()
+			.filter(|(expiry, _)| *expiry > now && *expiry <= until)
+			.flat_map(|(expiry, memberships)| {
+				memberships.into_iter().map(move |(org, account)| (org, account, expiry))
+			})
+			.collect();
+		expiring.sort_by(|a, b| a.2.cmp(&b.2));
+		expiring
+	}
+
+	/// Returns true if and only if the account is a member of an organization, whatever its role.
+	/// Only the organizations the account was added to are checked.
 	pub fn part_of_organization(account: &T::AccountId) -> bool {
-		let orgs = <Module<T>>::organizations();
-		for org in orgs.iter() {
-			if <did::Module<T>>::valid_delegate(org, &b"OrgMember".to_vec(), &account).is_ok() {
-				return true
+		Self::organizations_of(account).iter().any(|org| {
+			Self::role_of(org, account).map_or(false, |role| Self::has_role(org, account, role))
+		})
+	}
+
+	/// Returns true if and only if the account is an issuer of an organization.
+	pub fn issuer_of_organization(account: &T::AccountId) -> bool {
+		Self::organizations_of(account)
+			.iter()
+			.any(|org| Self::has_role(org, account, OrgRole::Issuer))
+	}
+
+	/// The organization the account acts for, if it holds `role` in it, or any valid role if `None`.
+	/// The selected acting organization takes precedence, otherwise the first matching one is used.
+	pub fn organization_acted_for(account: &T::AccountId, role: Option<OrgRole>) -> Option<T::AccountId> {
+		let holds = |org: &T::AccountId| match role {
+			Some(role) => Self::has_role(org, account, role),
+			None => Self::holds_any_role(org, account),
+		};
+
+		match Self::acting_organization(account) {
+			Some(org) => Some(org).filter(|org| holds(org)),
+			None => Self::organizations_of(account).into_iter().find(|org| holds(org)),
+		}
+	}
+
+	/// Moves the organizations from the `Vec` of V1 to the map of V2 and builds
+	/// the member index of every organization from `OrganizationsOf`.
+	pub fn migrate_to_v2() -> Weight {
+		let mut reads: Weight = 1;
+		let mut writes: Weight = 1;
+
+		if let Some(orgs) = take_storage_value::<Vec<T::AccountId>>(b"registrar", b"Organizations", &[]) {
+			for org in orgs.iter() {
+				<Organizations<T>>::insert(org, true);
+			}
+			writes += orgs.len() as Weight + 1;
+		}
+
+		for (account, orgs) in <OrganizationsOf<T>>::iter() {
+			reads += 1;
+			for org in orgs.iter() {
+				reads += 1;
+				if !Self::members_of(org).contains(&account) {
+					<MembersOf<T>>::append(org, &account);
+					writes += 1;
+				}
+			}
+		}
+
+		StorageVersion::put(Releases::V2);
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// Replaces the `OrgMember` delegate of every member by an `OrgIssuer` one, as members
+	/// could issue credentials before roles existed. Members that can't be migrated keep
+	/// their `OrgMember` delegate and are logged.
+	pub fn migrate_to_v3() -> Weight {
+		let mut reads: Weight = 0;
+		let mut writes: Weight = 1;
+
+		for (org, members) in <MembersOf<T>>::iter() {
+			let owner = <did::Module<T>>::identity_owner(&org);
+			reads += 2;
+			for account in members.iter() {
+				reads += 1;
+				if <did::Module<T>>::valid_listed_delegate(&org, b"OrgMember", account).is_err() {
+					continue;
+				}
+
+				// Grant the role first so that a failure leaves the old delegate in place.
+				reads += 2;
+				if let Err(e) = Self::grant_role(&org, account, OrgRole::Issuer, None) {
+					debug::warn!("registrar: can't grant a role to member {:?} of {:?}: {:?}", account, org, e);
+					continue;
+				}
+				writes += 2;
+
+				reads += 2;
+				match <did::Module<T>>::expire_delegate(&owner, &org, account, b"OrgMember") {
+					Ok(()) => writes += 1,
+					Err(e) => debug::warn!("registrar: can't expire the delegate of member {:?} of {:?}: {:?}", account, org, e),
+				}
 			}
 		}
-		false
+
+		StorageVersion::put(Releases::V3);
+		T::DbWeight::get().reads_writes(reads, writes)
 	}
 }
 
 /// Ensure that a consortium member is invoking a dispatch.
+/// Resolves to the member and the organization it acts for.
 // https://substrate.dev/rustdocs/v2.0.0-rc4/frame_support/traits/trait.EnsureOrigin.html
 pub struct EnsureOrg<T>(sp_std::marker::PhantomData<T>);
 impl<T: Trait> EnsureOrigin<T::Origin> for EnsureOrg<T> {
-	type Success = T::AccountId;
+	type Success = (T::AccountId, T::AccountId);
 	fn try_origin(o: T::Origin) -> Result<Self::Success, T::Origin> {
+		o.into().and_then(|o| match o {
+			RawOrigin::Signed(who) => match <Module<T>>::organization_acted_for(&who, None) {
+				Some(org) => Ok((who, org)),
+				None => Err(T::Origin::from(RawOrigin::Signed(who))),
+			},
+			r => Err(T::Origin::from(r)),
+		})
+	}
 
-		if_std! {
-			println!("inside custom origin");
-		}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> T::Origin {
+		T::Origin::from(RawOrigin::Signed(Default::default()))
+	}
+}
 
+/// Ensure that an issuer of a consortium organization is invoking a dispatch.
+/// Resolves to the issuer and the organization it acts for.
+pub struct EnsureOrgIssuer<T>(sp_std::marker::PhantomData<T>);
+impl<T: Trait> EnsureOrigin<T::Origin> for EnsureOrgIssuer<T> {
+	type Success = (T::AccountId, T::AccountId);
+	fn try_origin(o: T::Origin) -> Result<Self::Success, T::Origin> {
 		o.into().and_then(|o| match o {
-			RawOrigin::Signed(ref who)
-				if  <Module<T>>::part_of_organization(&who) => Ok(who.clone()),
-				r => Err(T::Origin::from(r)),
+			RawOrigin::Signed(who) => match <Module<T>>::organization_acted_for(&who, Some(OrgRole::Issuer)) {
+				Some(org) => Ok((who, org)),
+				None => Err(T::Origin::from(RawOrigin::Signed(who))),
+			},
+			r => Err(T::Origin::from(r)),
 		})
 	}
 
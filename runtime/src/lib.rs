@@ -45,6 +45,9 @@ pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
 pub use timestamp::Call as TimestampCall;
 
+#[cfg(test)]
+mod tests;
+
 /// An index to a block.
 pub type BlockNumber = u64;
 
@@ -68,6 +71,9 @@ pub type Index = u64;
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
+/// A timestamp: milliseconds since the unix epoch.
+pub type Moment = u64;
+
 /// Digest item type.
 pub type DigestItem = generic::DigestItem<Hash>;
 
@@ -220,8 +226,7 @@ parameter_types! {
 }
 
 impl timestamp::Trait for Runtime {
-	/// A timestamp: milliseconds since the unix epoch.
-	type Moment = u64;
+	type Moment = Moment;
 	type OnTimestampSet = Aura;
 	type MinimumPeriod = MinimumPeriod;
 	type WeightInfo = ();
@@ -265,13 +270,47 @@ impl pallet_did::Trait for Runtime {
 	type Signature = Signature;
 }
 
+parameter_types! {
+	pub const OrganizationApplicationDeposit: Balance = 1_000;
+	pub const OrganizationInvitationPeriod: BlockNumber = 7 * DAYS;
+	pub const OrganizationMaxMembershipDuration: BlockNumber = 365 * DAYS;
+}
+
 impl registrar::Trait for Runtime {
 	type Event = Event;
+	type Currency = Balances;
+	type ApplicationDeposit = OrganizationApplicationDeposit;
+	type ApproveOrigin = frame_system::EnsureRoot<AccountId>;
+	type InvitationPeriod = OrganizationInvitationPeriod;
+	type MaxMembershipDuration = OrganizationMaxMembershipDuration;
 }
 
 impl pallet_schema::Trait for Runtime {
 	type Event = Event;
-	type CreateRoleOrigin = registrar::EnsureOrg<Runtime>;
+	type CreateRoleOrigin = registrar::EnsureOrgIssuer<Runtime>;
+}
+
+parameter_types! {
+	pub const CredentialMaxProps: u32 = 16;
+	pub const CredentialMaxPropKeyLength: u32 = 32;
+	pub const CredentialMaxPropValueLength: u32 = 256;
+}
+
+/// Accepts issuers of an organization, including those inherited from a parent organization.
+pub struct OrgIssuerCheck;
+impl pallet_credential::IssuerCheck<AccountId> for OrgIssuerCheck {
+	fn is_issuer(issuer: &AccountId, who: &AccountId) -> bool {
+		Registrar::has_role(issuer, who, registrar::OrgRole::Issuer)
+	}
+}
+
+impl pallet_credential::Trait for Runtime {
+	type Event = Event;
+	type CreateRoleOrigin = registrar::EnsureOrgIssuer<Runtime>;
+	type IssuerCheck = OrgIssuerCheck;
+	type MaxProps = CredentialMaxProps;
+	type MaxPropKeyLength = CredentialMaxPropKeyLength;
+	type MaxPropValueLength = CredentialMaxPropValueLength;
 }
 
 impl rbac::Trait for Runtime {
@@ -314,8 +353,9 @@ construct_runtime!(
 		TransactionPayment: pallet_transaction_payment::{Module, Storage},
 		Sudo: sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		SchemaRegistry: pallet_schema::{Module, Call, Storage, Event<T>},
-		Registrar: registrar::{Module, Call, Storage, Event<T>},
+		Registrar: registrar::{Module, Call, Storage, Event<T>, Config},
 		Rbac: rbac::{Module, Call, Storage, Event<T>, Config<T>},
+		CredentialRegistry: pallet_credential::{Module, Call, Storage, Event<T>},
 	}
 );
 
@@ -431,6 +471,50 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_credential_rpc_runtime_api::CredentialApi<Block, AccountId, Hash, Moment> for Runtime {
+		fn verify_credential(
+			lookup: pallet_credential::CredentialLookup<Hash>,
+		) -> Vec<pallet_credential::CredentialVerification<AccountId, Hash, Moment>> {
+			CredentialRegistry::verify_credential(lookup)
+		}
+
+		fn status_list(
+			id: pallet_credential::StatusListId,
+		) -> Option<pallet_credential::StatusListSnapshot<AccountId, Hash>> {
+			CredentialRegistry::status_list_snapshot(&id)
+		}
+	}
+
+	impl registrar_rpc_runtime_api::RegistrarApi<Block, AccountId, Hash, BlockNumber> for Runtime {
+		fn organization_profile(org: AccountId) -> Option<registrar::OrgProfile<AccountId, Hash>> {
+			Registrar::organization_profile(org)
+		}
+
+		fn organizations(offset: u32, limit: u32) -> Vec<AccountId> {
+			Registrar::organizations_page(offset, limit)
+		}
+
+		fn organization_members(org: AccountId) -> Vec<AccountId> {
+			Registrar::members_of(org)
+		}
+
+		fn organizations_of(account: AccountId) -> Vec<AccountId> {
+			Registrar::organizations_of(account)
+		}
+
+		fn membership(org: AccountId, account: AccountId) -> Option<registrar::Membership<AccountId, BlockNumber>> {
+			Registrar::membership(&org, &account)
+		}
+
+		fn organization_tree(root: AccountId) -> Vec<(AccountId, u32)> {
+			Registrar::organization_tree(&root)
+		}
+
+		fn memberships_expiring_within(blocks: BlockNumber) -> Vec<(AccountId, AccountId, BlockNumber)> {
+			Registrar::memberships_expiring_within(blocks)
+		}
+	}
+
 	impl fg_primitives::GrandpaApi<Block> for Runtime {
 		fn grandpa_authorities() -> GrandpaAuthorityList {
 			Grandpa::grandpa_authorities()
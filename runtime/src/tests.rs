@@ -0,0 +1,831 @@
+// End-to-end tests exercising the pallets wired into the runtime.
+
+use crate::*;
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::migration::put_storage_value,
+	traits::{EnsureOrigin, OnInitialize, OnRuntimeUpgrade},
+	StorageMap,
+};
+use sp_core::{sr25519, Pair};
+use registrar::{OrgRole, Releases};
+use sp_runtime::{traits::IdentifyAccount, DispatchError};
+
+const TEST_ORG_NAME: &[u8] = b"Dhiway Test";
+const TEST_CRED_ID: &[u8] = b"00012345600012";
+const TEST_SCHEMA_ID: &[u8] = b"00098765400098";
+
+fn pair(seed: &str) -> sr25519::Pair {
+	sr25519::Pair::from_string(&format!("//{}", seed), None).expect("static values are valid; qed")
+}
+
+fn account(seed: &str) -> AccountId {
+	MultiSigner::from(pair(seed).public()).into_account()
+}
+
+fn issuer_signature(seed: &str, id: &[u8], hash: &Hash, schema_id: &[u8]) -> Signature {
+	let payload = CredentialRegistry::credential_signing_payload(id, hash, &None, schema_id);
+	Signature::from(pair(seed).sign(&payload))
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+	balances::GenesisConfig::<Runtime> {
+		balances: vec![(account("Org"), 10_000), (account("Applicant"), 10_000)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	registrar::GenesisConfig::default()
+		.assimilate_storage::<Runtime>(&mut storage)
+		.unwrap();
+
+	let mut ext = sp_io::TestExternalities::from(storage);
+	// Events are not emitted on block 0 -> advance to block 1.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn add_member(admin: &AccountId, org: &AccountId, account: &AccountId, role: OrgRole) {
+	assert_ok!(Registrar::invite_to_organization(
+		Origin::signed(admin.clone()),
+		org.clone(),
+		account.clone(),
+		role,
+		None,
+	));
+	assert_ok!(Registrar::accept_invitation(Origin::signed(account.clone()), org.clone()));
+}
+
+fn create_organization(org: &AccountId) {
+	assert_ok!(Registrar::apply_for_organization(
+		Origin::signed(org.clone()),
+		TEST_ORG_NAME.to_vec(),
+		Hash::from_low_u64_be(42),
+	));
+	assert_ok!(Registrar::approve_organization(Origin::root(), org.clone()));
+}
+
+#[test]
+fn organization_member_registers_and_revokes_credential() {
+	new_test_ext().execute_with(|| {
+		let org = account("Org");
+		let member = account("Member");
+		let id = TEST_CRED_ID.to_vec();
+		let hash = Hash::from_low_u64_be(1);
+
+		create_organization(&org);
+		add_member(&org, &org, &member, OrgRole::Issuer);
+		assert_ok!(SchemaRegistry::register_schema(
+			Origin::signed(member.clone()),
+			TEST_SCHEMA_ID.to_vec(),
+			Hash::from_low_u64_be(100),
+			None,
+		));
+
+		assert_ok!(CredentialRegistry::register_credential(
+			Origin::signed(member.clone()),
+			id.clone(),
+			org.clone(),
+			hash,
+			None,
+			TEST_SCHEMA_ID.to_vec(),
+			None,
+			None,
+			None,
+			None,
+			org.clone(),
+			issuer_signature("Org", &id, &hash, TEST_SCHEMA_ID),
+		));
+		assert!(CredentialRegistry::cred_by_id(&id).is_some());
+		assert_eq!(CredentialRegistry::issuer_of_cred(&id), Some(org.clone()));
+		assert!(System::events().iter().any(|er| er.event
+			== Event::pallet_credential(pallet_credential::RawEvent::CredentialRegistered(
+				member.clone(),
+				id.clone(),
+				hash,
+			))));
+
+		assert_ok!(CredentialRegistry::revoke_credential(
+			Origin::signed(member.clone()),
+			id.clone(),
+			org.clone(),
+			hash,
+			None,
+		));
+		assert_eq!(
+			CredentialRegistry::credential_status(&id),
+			Some(pallet_credential::CredentialStatus::Revoked)
+		);
+		assert!(System::events().iter().any(|er| er.event
+			== Event::pallet_credential(pallet_credential::RawEvent::CredentialRevoked(
+				member.clone(),
+				id.clone(),
+				hash,
+			))));
+	});
+}
+
+#[test]
+fn non_member_cannot_register_credential() {
+	new_test_ext().execute_with(|| {
+		let org = account("Org");
+		let outsider = account("Outsider");
+
+		create_organization(&org);
+
+		assert_noop!(
+			CredentialRegistry::register_credential(
+				Origin::signed(outsider),
+				TEST_CRED_ID.to_vec(),
+				org,
+				Hash::from_low_u64_be(1),
+				None,
+				TEST_SCHEMA_ID.to_vec(),
+				None,
+				None,
+				None,
+				None,
+				account("Org"),
+				issuer_signature("Org", TEST_CRED_ID, &Hash::from_low_u64_be(1), TEST_SCHEMA_ID),
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn removed_members_lose_organization_rights() {
+	new_test_ext().execute_with(|| {
+		let org = account("Org");
+		let member = account("Member");
+		let other = account("Other");
+
+		create_organization(&org);
+		add_member(&org, &org, &member, OrgRole::Issuer);
+		add_member(&org, &org, &other, OrgRole::Viewer);
+		assert!(Registrar::part_of_organization(&member));
+
+		assert_ok!(Registrar::remove_from_organization(Origin::signed(org.clone()), org.clone(), member.clone()));
+		assert!(!Registrar::part_of_organization(&member));
+		assert!(Registrar::organizations_of(&member).is_empty());
+		assert_eq!(Registrar::members_of(&org), vec![other.clone()]);
+		assert_noop!(
+			SchemaRegistry::register_schema(
+				Origin::signed(member.clone()),
+				TEST_SCHEMA_ID.to_vec(),
+				Hash::from_low_u64_be(100),
+				None,
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Registrar::remove_from_organization(Origin::signed(org.clone()), org.clone(), member),
+			registrar::Error::<Runtime>::NotMemberOfOrganization
+		);
+
+		assert_ok!(Registrar::dissolve_organization(Origin::signed(org.clone())));
+		assert!(!Registrar::part_of_organization(&other));
+		assert!(!Registrar::is_organization(&org));
+		assert!(System::events().iter().any(|er| er.event
+			== Event::registrar(registrar::RawEvent::DissolvedOrganization(org.clone()))));
+	});
+}
+
+#[test]
+fn organization_maintains_its_profile() {
+	new_test_ext().execute_with(|| {
+		let org = account("Org");
+		let contact = account("Contact");
+		let logo = Hash::from_low_u64_be(7);
+
+		assert_noop!(
+			Registrar::set_organization_profile(
+				Origin::signed(org.clone()),
+				b"Dhiway Networks Pvt Ltd".to_vec(),
+				b"U72900KA2019PTC123456".to_vec(),
+				b"IN-KA".to_vec(),
+				b"https://dhiway.com".to_vec(),
+				None,
+				None,
+			),
+			registrar::Error::<Runtime>::InvalidOrganization
+		);
+		create_organization(&org);
+		assert_noop!(
+			Registrar::set_organization_profile(Origin::signed(org.clone()), vec![], vec![], vec![], vec![], None, None),
+			registrar::Error::<Runtime>::InvalidLegalName
+		);
+
+		assert_ok!(Registrar::set_organization_profile(
+			Origin::signed(org.clone()),
+			b"Dhiway Networks Pvt Ltd".to_vec(),
+			b"U72900KA2019PTC123456".to_vec(),
+			b"IN-KA".to_vec(),
+			b"https://dhiway.com".to_vec(),
+			None,
+			None,
+		));
+		assert_ok!(Registrar::update_organization_logo(Origin::signed(org.clone()), Some(logo)));
+		assert_ok!(Registrar::update_organization_contact(
+			Origin::signed(org.clone()),
+			b"https://cord.network".to_vec(),
+			Some(contact.clone()),
+		));
+
+		let profile = Registrar::organization_profile(&org).unwrap();
+		assert_eq!(profile.legal_name, b"Dhiway Networks Pvt Ltd".to_vec());
+		assert_eq!(profile.website, b"https://cord.network".to_vec());
+		assert_eq!(profile.logo_hash, Some(logo));
+		assert_eq!(profile.contact_did, Some(contact));
+	});
+}
+
+#[test]
+fn organizations_are_only_created_once_approved() {
+	new_test_ext().execute_with(|| {
+		let applicant = account("Applicant");
+		let member = account("Member");
+		let deposit = OrganizationApplicationDeposit::get();
+
+		assert_ok!(Registrar::apply_for_organization(
+			Origin::signed(applicant.clone()),
+			TEST_ORG_NAME.to_vec(),
+			Hash::from_low_u64_be(42),
+		));
+		assert_eq!(Balances::reserved_balance(&applicant), deposit);
+		assert_noop!(
+			Registrar::invite_to_organization(
+				Origin::signed(applicant.clone()),
+				applicant.clone(),
+				member.clone(),
+				OrgRole::Viewer,
+				None,
+			),
+			registrar::Error::<Runtime>::InvalidOrganization
+		);
+		assert_noop!(
+			Registrar::approve_organization(Origin::signed(applicant.clone()), applicant.clone()),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(Registrar::reject_organization(Origin::root(), applicant.clone()));
+		assert_eq!(Balances::reserved_balance(&applicant), 0);
+		assert_eq!(Balances::free_balance(&applicant), 10_000 - deposit);
+		assert!(!Registrar::is_organization(&applicant));
+
+		assert_ok!(Registrar::apply_for_organization(
+			Origin::signed(applicant.clone()),
+			TEST_ORG_NAME.to_vec(),
+			Hash::from_low_u64_be(43),
+		));
+		assert_ok!(Registrar::approve_organization(Origin::root(), applicant.clone()));
+		assert_eq!(Balances::reserved_balance(&applicant), 0);
+		assert!(Registrar::is_organization(&applicant));
+		add_member(&applicant, &applicant, &member, OrgRole::Viewer);
+
+		// A dissolved organization may apply again, its `Org` DID attribute is re-validated.
+		assert_ok!(Registrar::dissolve_organization(Origin::signed(applicant.clone())));
+		assert!(PalletDid::valid_attribute(&applicant, b"Org", TEST_ORG_NAME).is_err());
+		assert_ok!(Registrar::apply_for_organization(
+			Origin::signed(applicant.clone()),
+			b"Dhiway Networks".to_vec(),
+			Hash::from_low_u64_be(44),
+		));
+		assert_ok!(Registrar::approve_organization(Origin::root(), applicant.clone()));
+		assert_eq!(Balances::reserved_balance(&applicant), 0);
+		assert!(Registrar::is_organization(&applicant));
+		assert_ok!(PalletDid::valid_attribute(&applicant, b"Org", b"Dhiway Networks"));
+	});
+}
+
+#[test]
+fn applicant_without_funds_cannot_apply() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Registrar::apply_for_organization(
+				Origin::signed(account("Member")),
+				TEST_ORG_NAME.to_vec(),
+				Hash::from_low_u64_be(42),
+			),
+			registrar::Error::<Runtime>::InsufficientDeposit
+		);
+	});
+}
+
+#[test]
+fn only_issuers_register_and_only_admins_manage_members() {
+	new_test_ext().execute_with(|| {
+		let org = account("Org");
+		let admin = account("Admin");
+		let issuer = account("Issuer");
+		let viewer = account("Viewer");
+
+		create_organization(&org);
+		add_member(&org, &org, &admin, OrgRole::Admin);
+		add_member(&admin, &org, &issuer, OrgRole::Issuer);
+		add_member(&admin, &org, &viewer, OrgRole::Viewer);
+		assert_eq!(Registrar::role_of(&org, &issuer), Some(OrgRole::Issuer));
+		assert!(PalletDid::valid_listed_delegate(&org, b"OrgIssuer", &issuer).is_ok());
+		assert!(Registrar::part_of_organization(&viewer));
+
+		assert_noop!(
+			Registrar::invite_to_organization(Origin::signed(issuer.clone()), org.clone(), account("Other"), OrgRole::Viewer, None),
+			registrar::Error::<Runtime>::NotOrganizationAdmin
+		);
+		assert_noop!(
+			Registrar::remove_from_organization(Origin::signed(viewer.clone()), org.clone(), issuer.clone()),
+			registrar::Error::<Runtime>::NotOrganizationAdmin
+		);
+
+		for who in [admin.clone(), viewer.clone()].iter() {
+			assert_noop!(
+				SchemaRegistry::register_schema(
+					Origin::signed(who.clone()),
+					TEST_SCHEMA_ID.to_vec(),
+					Hash::from_low_u64_be(100),
+					None,
+				),
+				DispatchError::BadOrigin
+			);
+		}
+		assert_ok!(SchemaRegistry::register_schema(
+			Origin::signed(issuer.clone()),
+			TEST_SCHEMA_ID.to_vec(),
+			Hash::from_low_u64_be(100),
+			None,
+		));
+
+		assert_ok!(Registrar::set_member_role(Origin::signed(admin.clone()), org.clone(), issuer.clone(), OrgRole::Viewer));
+		assert!(PalletDid::valid_listed_delegate(&org, b"OrgIssuer", &issuer).is_err());
+		assert!(!Registrar::issuer_of_organization(&issuer));
+		assert!(Registrar::part_of_organization(&issuer));
+		assert!(System::events().iter().any(|er| er.event
+			== Event::registrar(registrar::RawEvent::MemberRoleChanged(org.clone(), issuer.clone(), OrgRole::Viewer))));
+	});
+}
+
+#[test]
+fn members_join_by_accepting_invitations_and_may_leave() {
+	new_test_ext().execute_with(|| {
+		let org = account("Org");
+		let member = account("Member");
+		let late = account("Late");
+
+		create_organization(&org);
+		assert_ok!(Registrar::invite_to_organization(
+			Origin::signed(org.clone()),
+			org.clone(),
+			member.clone(),
+			OrgRole::Issuer,
+			None,
+		));
+		assert!(!Registrar::part_of_organization(&member));
+		assert_noop!(
+			Registrar::invite_to_organization(Origin::signed(org.clone()), org.clone(), member.clone(), OrgRole::Viewer, None),
+			registrar::Error::<Runtime>::InvitationExists
+		);
+		assert_noop!(
+			Registrar::accept_invitation(Origin::signed(late.clone()), org.clone()),
+			registrar::Error::<Runtime>::InvitationMissing
+		);
+
+		assert_ok!(Registrar::accept_invitation(Origin::signed(member.clone()), org.clone()));
+		assert!(Registrar::issuer_of_organization(&member));
+		assert!(Registrar::invitation(&org, &member).is_none());
+
+		assert_ok!(Registrar::invite_to_organization(
+			Origin::signed(org.clone()),
+			org.clone(),
+			late.clone(),
+			OrgRole::Viewer,
+			None,
+		));
+		System::set_block_number(1 + OrganizationInvitationPeriod::get());
+		assert_noop!(
+			Registrar::accept_invitation(Origin::signed(late.clone()), org.clone()),
+			registrar::Error::<Runtime>::InvitationExpired
+		);
+
+		assert_ok!(Registrar::leave_organization(Origin::signed(member.clone()), org.clone()));
+		assert!(!Registrar::part_of_organization(&member));
+		assert!(Registrar::members_of(&org).is_empty());
+		assert!(System::events().iter().any(|er| er.event
+			== Event::registrar(registrar::RawEvent::LeftOrganization(org.clone(), member.clone()))));
+		assert_noop!(
+			Registrar::leave_organization(Origin::signed(member), org),
+			registrar::Error::<Runtime>::NotMemberOfOrganization
+		);
+	});
+}
+
+#[test]
+fn time_limited_memberships_lapse_unless_renewed() {
+	new_test_ext().execute_with(|| {
+		let org = account("Org");
+		let contractor = account("Contractor");
+
+		create_organization(&org);
+		assert_noop!(
+			Registrar::invite_to_organization(Origin::signed(org.clone()), org.clone(), contractor.clone(), OrgRole::Issuer, Some(0)),
+			registrar::Error::<Runtime>::InvalidDuration
+		);
+		let too_long = Some(OrganizationMaxMembershipDuration::get() + 1);
+		assert_noop!(
+			Registrar::invite_to_organization(Origin::signed(org.clone()), org.clone(), contractor.clone(), OrgRole::Issuer, too_long),
+			registrar::Error::<Runtime>::InvalidDuration
+		);
+		assert_ok!(Registrar::invite_to_organization(
+			Origin::signed(org.clone()),
+			org.clone(),
+			contractor.clone(),
+			OrgRole::Issuer,
+			Some(10),
+		));
+		assert_ok!(Registrar::accept_invitation(Origin::signed(contractor.clone()), org.clone()));
+		assert_eq!(Registrar::membership_expiry(&org, &contractor), Some(11));
+		assert_eq!(Registrar::memberships_expiring_within(10), vec![(org.clone(), contractor.clone(), 11)]);
+
+		assert_noop!(
+			Registrar::renew_membership(Origin::signed(org.clone()), org.clone(), contractor.clone(), too_long.unwrap()),
+			registrar::Error::<Runtime>::InvalidDuration
+		);
+		assert_ok!(Registrar::renew_membership(Origin::signed(org.clone()), org.clone(), contractor.clone(), 5));
+		assert_eq!(Registrar::membership_expiry(&org, &contractor), Some(16));
+		assert!(Registrar::memberships_expiring_within(10).is_empty());
+		assert!(Registrar::expiring_memberships(11).is_empty());
+
+		System::set_block_number(11);
+		Registrar::on_initialize(11);
+		assert!(Registrar::issuer_of_organization(&contractor));
+
+		System::set_block_number(16);
+		Registrar::on_initialize(16);
+		assert!(!Registrar::part_of_organization(&contractor));
+		assert!(Registrar::members_of(&org).is_empty());
+		assert_eq!(Registrar::membership_expiry(&org, &contractor), None);
+		assert!(System::events().iter().any(|er| er.event
+			== Event::registrar(registrar::RawEvent::MembershipExpired(org.clone(), contractor.clone()))));
+	});
+}
+
+#[test]
+fn memberships_lapsing_per_block_are_capped() {
+	new_test_ext().execute_with(|| {
+		let org = account("Org");
+		let contractor = account("Contractor");
+		let other = account("Other");
+		let full = vec![(org.clone(), other.clone()); registrar::MAX_EXPIRIES_PER_BLOCK];
+
+		create_organization(&org);
+		registrar::ExpiringMemberships::<Runtime>::insert(11, full.clone());
+		assert_ok!(Registrar::invite_to_organization(
+			Origin::signed(org.clone()),
+			org.clone(),
+			contractor.clone(),
+			OrgRole::Issuer,
+			Some(10),
+		));
+		assert_noop!(
+			Registrar::accept_invitation(Origin::signed(contractor.clone()), org.clone()),
+			registrar::Error::<Runtime>::ExpiryBlockFull
+		);
+
+		registrar::ExpiringMemberships::<Runtime>::remove(11);
+		assert_ok!(Registrar::accept_invitation(Origin::signed(contractor.clone()), org.clone()));
+		assert_eq!(Registrar::membership_expiry(&org, &contractor), Some(11));
+
+		registrar::ExpiringMemberships::<Runtime>::insert(16, full);
+		assert_noop!(
+			Registrar::renew_membership(Origin::signed(org.clone()), org.clone(), contractor.clone(), 5),
+			registrar::Error::<Runtime>::ExpiryBlockFull
+		);
+		assert_ok!(Registrar::renew_membership(Origin::signed(org.clone()), org.clone(), contractor.clone(), 6));
+		assert_eq!(Registrar::membership_expiry(&org, &contractor), Some(17));
+	});
+}
+
+#[test]
+fn members_act_for_the_selected_organization() {
+	new_test_ext().execute_with(|| {
+		let org = account("Org");
+		let other_org = account("Applicant");
+		let member = account("Member");
+
+		create_organization(&org);
+		create_organization(&other_org);
+		add_member(&org, &org, &member, OrgRole::Issuer);
+		add_member(&other_org, &other_org, &member, OrgRole::Issuer);
+		assert_eq!(Registrar::organization_acted_for(&member, Some(OrgRole::Issuer)), Some(org.clone()));
+
+		assert_noop!(
+			Registrar::set_acting_organization(Origin::signed(member.clone()), Some(account("Outsider"))),
+			registrar::Error::<Runtime>::NotMemberOfOrganization
+		);
+		assert_ok!(Registrar::set_acting_organization(Origin::signed(member.clone()), Some(other_org.clone())));
+		assert_ok!(SchemaRegistry::register_schema(
+			Origin::signed(member.clone()),
+			TEST_SCHEMA_ID.to_vec(),
+			Hash::from_low_u64_be(100),
+			None,
+		));
+		assert_eq!(SchemaRegistry::owner_of_schema(TEST_SCHEMA_ID.to_vec()), Some(other_org.clone()));
+		assert_noop!(
+			CredentialRegistry::register_credential(
+				Origin::signed(member.clone()),
+				TEST_CRED_ID.to_vec(),
+				org.clone(),
+				Hash::from_low_u64_be(1),
+				None,
+				TEST_SCHEMA_ID.to_vec(),
+				None,
+				None,
+				None,
+				None,
+				org.clone(),
+				issuer_signature("Org", TEST_CRED_ID, &Hash::from_low_u64_be(1), TEST_SCHEMA_ID),
+			),
+			pallet_credential::Error::<Runtime>::CredNotActingOrganization
+		);
+
+		// Leaving the acting organization clears the selection.
+		assert_ok!(Registrar::leave_organization(Origin::signed(member.clone()), other_org.clone()));
+		assert_eq!(Registrar::acting_organization(&member), None);
+		assert_eq!(Registrar::organization_acted_for(&member, None), Some(org));
+	});
+}
+
+#[test]
+fn sub_organizations_inherit_configured_roles() {
+	new_test_ext().execute_with(|| {
+		let org = account("Org");
+		let issuer = account("Issuer");
+		let viewer = account("Viewer");
+
+		create_organization(&org);
+		add_member(&org, &org, &issuer, OrgRole::Issuer);
+		add_member(&org, &org, &viewer, OrgRole::Viewer);
+		assert_ok!(Registrar::create_sub_organization(
+			Origin::signed(org.clone()),
+			org.clone(),
+			b"Logistics".to_vec(),
+			vec![OrgRole::Issuer],
+		));
+		let sub = Registrar::sub_organization_id(&org, b"Logistics").unwrap();
+		assert!(Registrar::is_organization(&sub));
+		assert_eq!(Registrar::parent_of(&sub), Some(org.clone()));
+		assert_eq!(PalletDid::identity_owner(&sub), org);
+		assert_noop!(
+			Registrar::create_sub_organization(Origin::signed(issuer.clone()), org.clone(), b"Sales".to_vec(), vec![]),
+			registrar::Error::<Runtime>::NotOrganizationAdmin
+		);
+
+		assert_ok!(Registrar::create_sub_organization(
+			Origin::signed(org.clone()),
+			sub.clone(),
+			b"Fleet".to_vec(),
+			vec![],
+		));
+		let fleet = Registrar::sub_organization_id(&sub, b"Fleet").unwrap();
+		assert_eq!(Registrar::organization_tree(&org), vec![(org.clone(), 0), (sub.clone(), 1), (fleet.clone(), 2)]);
+
+		// Issuers of the parent may issue for the sub-organization, viewers hold nothing there.
+		assert!(Registrar::has_role(&sub, &issuer, OrgRole::Issuer));
+		assert!(!Registrar::has_role(&fleet, &issuer, OrgRole::Issuer));
+		assert_noop!(
+			Registrar::set_acting_organization(Origin::signed(viewer.clone()), Some(sub.clone())),
+			registrar::Error::<Runtime>::NotMemberOfOrganization
+		);
+		assert_ok!(Registrar::set_acting_organization(Origin::signed(issuer.clone()), Some(sub.clone())));
+		assert_ok!(SchemaRegistry::register_schema(
+			Origin::signed(issuer.clone()),
+			TEST_SCHEMA_ID.to_vec(),
+			Hash::from_low_u64_be(100),
+			None,
+		));
+		assert_eq!(SchemaRegistry::owner_of_schema(TEST_SCHEMA_ID.to_vec()), Some(sub.clone()));
+
+		// ...and manage the credentials they issued for it.
+		let id = TEST_CRED_ID.to_vec();
+		let hash = Hash::from_low_u64_be(1);
+		assert_ok!(CredentialRegistry::register_credential(
+			Origin::signed(issuer.clone()),
+			id.clone(),
+			sub.clone(),
+			hash,
+			None,
+			TEST_SCHEMA_ID.to_vec(),
+			None,
+			None,
+			None,
+			None,
+			org.clone(),
+			issuer_signature("Org", &id, &hash, TEST_SCHEMA_ID),
+		));
+		assert_eq!(CredentialRegistry::issuer_of_cred(&id), Some(sub.clone()));
+		assert_ok!(CredentialRegistry::revoke_credential(
+			Origin::signed(issuer.clone()),
+			id.clone(),
+			sub.clone(),
+			hash,
+			None,
+		));
+		assert_eq!(
+			CredentialRegistry::credential_status(&id),
+			Some(pallet_credential::CredentialStatus::Revoked)
+		);
+
+		assert_ok!(Registrar::set_inherited_roles(Origin::signed(org.clone()), sub.clone(), vec![OrgRole::Viewer]));
+		assert!(!Registrar::has_role(&sub, &issuer, OrgRole::Issuer));
+		assert!(Registrar::has_role(&sub, &viewer, OrgRole::Viewer));
+
+		assert_noop!(
+			Registrar::dissolve_sub_organization(Origin::signed(org.clone()), sub.clone()),
+			registrar::Error::<Runtime>::HasSubOrganizations
+		);
+		assert_ok!(Registrar::dissolve_sub_organization(Origin::signed(org.clone()), fleet.clone()));
+		assert_ok!(Registrar::dissolve_sub_organization(Origin::signed(org.clone()), sub.clone()));
+		assert!(!Registrar::is_organization(&sub));
+		assert!(Registrar::sub_organizations(&org).is_empty());
+		assert_eq!(Registrar::organization_tree(&org), vec![(org.clone(), 0)]);
+
+		// A dissolved sub-organization can be created again under the same name.
+		assert_ok!(Registrar::create_sub_organization(
+			Origin::signed(org.clone()),
+			org.clone(),
+			b"Logistics".to_vec(),
+			vec![OrgRole::Issuer],
+		));
+		assert!(Registrar::is_organization(&sub));
+		assert_eq!(PalletDid::identity_owner(&sub), org);
+		assert_ok!(PalletDid::valid_attribute(&sub, b"Org", b"Logistics"));
+		assert!(Registrar::has_role(&sub, &issuer, OrgRole::Issuer));
+		assert_eq!(Registrar::organization_tree(&org), vec![(org, 0), (sub, 1)]);
+	});
+}
+
+#[test]
+fn only_existing_organizations_can_be_acted_for() {
+	new_test_ext().execute_with(|| {
+		let org = account("Org");
+		let outsider = account("Outsider");
+		let is_org_issuer = |who: &AccountId| registrar::EnsureOrgIssuer::<Runtime>::try_origin(Origin::signed(who.clone())).is_ok();
+
+		// Owning its own DID doesn't make an account an organization.
+		assert_noop!(
+			Registrar::set_acting_organization(Origin::signed(outsider.clone()), Some(outsider.clone())),
+			registrar::Error::<Runtime>::NotMemberOfOrganization
+		);
+		assert!(!is_org_issuer(&outsider));
+		assert_noop!(
+			SchemaRegistry::register_schema(
+				Origin::signed(outsider),
+				TEST_SCHEMA_ID.to_vec(),
+				Hash::from_low_u64_be(100),
+				None,
+			),
+			DispatchError::BadOrigin
+		);
+
+		create_organization(&org);
+		assert_ok!(Registrar::create_sub_organization(
+			Origin::signed(org.clone()),
+			org.clone(),
+			b"Logistics".to_vec(),
+			vec![],
+		));
+		let sub = Registrar::sub_organization_id(&org, b"Logistics").unwrap();
+		assert_ok!(Registrar::set_acting_organization(Origin::signed(org.clone()), Some(sub.clone())));
+		assert!(is_org_issuer(&org));
+
+		// The parent still owns the DID of a dissolved sub-organization.
+		assert_ok!(Registrar::dissolve_sub_organization(Origin::signed(org.clone()), sub.clone()));
+		assert_eq!(PalletDid::identity_owner(&sub), org);
+		assert!(!is_org_issuer(&org));
+		assert_noop!(
+			Registrar::set_acting_organization(Origin::signed(org.clone()), Some(sub)),
+			registrar::Error::<Runtime>::NotMemberOfOrganization
+		);
+
+		assert_ok!(Registrar::set_acting_organization(Origin::signed(org.clone()), Some(org.clone())));
+		assert!(is_org_issuer(&org));
+		assert_ok!(Registrar::dissolve_organization(Origin::signed(org.clone())));
+		assert!(!is_org_issuer(&org));
+		assert_noop!(
+			Registrar::set_acting_organization(Origin::signed(org.clone()), Some(org.clone())),
+			registrar::Error::<Runtime>::NotMemberOfOrganization
+		);
+		assert_noop!(
+			SchemaRegistry::register_schema(
+				Origin::signed(org),
+				TEST_SCHEMA_ID.to_vec(),
+				Hash::from_low_u64_be(100),
+				None,
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn registrar_queries_list_organizations_and_memberships() {
+	new_test_ext().execute_with(|| {
+		let org = account("Org");
+		let other_org = account("Applicant");
+		let member = account("Member");
+
+		create_organization(&org);
+		create_organization(&other_org);
+		let mut orgs = vec![org.clone(), other_org.clone()];
+		orgs.sort();
+		assert_eq!(Registrar::organizations_page(0, 10), orgs);
+		assert_eq!(Registrar::organizations_page(1, 10), orgs[1..].to_vec());
+		assert_eq!(Registrar::organizations_page(0, 1), orgs[..1].to_vec());
+		assert!(Registrar::organizations_page(2, 10).is_empty());
+
+		assert_eq!(Registrar::membership(&org, &member), None);
+		assert_ok!(Registrar::invite_to_organization(
+			Origin::signed(org.clone()),
+			org.clone(),
+			member.clone(),
+			OrgRole::Viewer,
+			Some(10),
+		));
+		assert_ok!(Registrar::accept_invitation(Origin::signed(member.clone()), org.clone()));
+		assert_eq!(
+			Registrar::membership(&org, &member),
+			Some(registrar::Membership {
+				organization: org.clone(),
+				member: member.clone(),
+				role: OrgRole::Viewer,
+				expires: Some(11),
+				valid: true,
+			})
+		);
+		assert_eq!(Registrar::organizations_of(&member), vec![org.clone()]);
+		assert_eq!(Registrar::members_of(&org), vec![member.clone()]);
+		assert_eq!(Registrar::memberships_expiring_within(10), vec![(org.clone(), member.clone(), 11)]);
+		assert_eq!(Registrar::organization_tree(&org), vec![(org, 0)]);
+		assert!(Registrar::organization_tree(&member).is_empty());
+	});
+}
+
+#[test]
+fn organizations_migrate_to_a_map_once() {
+	new_test_ext().execute_with(|| {
+		let org = account("Org");
+		let member = account("Member");
+
+		// A fresh chain starts at the latest release and has nothing to migrate.
+		assert_eq!(Registrar::storage_version(), Releases::V3);
+		assert_eq!(Registrar::on_runtime_upgrade(), 0);
+
+		// V1 kept organizations in a single list and members only in `OrganizationsOf`.
+		put_storage_value(b"registrar", b"StorageVersion", &[], Releases::V1);
+		put_storage_value(b"registrar", b"Organizations", &[], vec![org.clone()]);
+		registrar::OrganizationsOf::<Runtime>::insert(&member, vec![org.clone()]);
+
+		assert!(Registrar::on_runtime_upgrade() > 0);
+		assert_eq!(Registrar::storage_version(), Releases::V3);
+		assert!(Registrar::is_organization(&org));
+		assert_eq!(Registrar::members_of(&org), vec![member.clone()]);
+
+		// Running the upgrade again changes nothing.
+		assert_eq!(Registrar::on_runtime_upgrade(), 0);
+		assert_eq!(Registrar::members_of(&org), vec![member]);
+	});
+}
+
+#[test]
+fn members_migrate_to_issuer_roles() {
+	new_test_ext().execute_with(|| {
+		let org = account("Org");
+		let member = account("Member");
+		let issuer = account("Issuer");
+		let member_type = b"OrgMember".to_vec();
+
+		// V2 gave every member the same `OrgMember` delegate.
+		create_organization(&org);
+		put_storage_value(b"registrar", b"StorageVersion", &[], Releases::V2);
+		assert_ok!(PalletDid::create_delegate(&org, &org, &member, &member_type, None));
+		assert_ok!(PalletDid::create_delegate(&org, &org, &issuer, &member_type, None));
+		// The issuer delegate already exists, so granting the role fails for this member.
+		assert_ok!(PalletDid::create_delegate(&org, &org, &issuer, &OrgRole::Issuer.delegate_type().to_vec(), None));
+		registrar::MembersOf::<Runtime>::insert(&org, vec![member.clone(), issuer.clone()]);
+
+		assert!(Registrar::on_runtime_upgrade() > 0);
+		assert_eq!(Registrar::storage_version(), Releases::V3);
+		assert_eq!(Registrar::role_of(&org, &member), Some(OrgRole::Issuer));
+		assert!(Registrar::has_role(&org, &member, OrgRole::Issuer));
+		assert!(PalletDid::valid_listed_delegate(&org, &member_type, &member).is_err());
+
+		// A member that couldn't be migrated keeps its old delegate.
+		assert_eq!(Registrar::role_of(&org, &issuer), None);
+		assert!(PalletDid::valid_listed_delegate(&org, &member_type, &issuer).is_ok());
+
+		// Running the upgrade again changes nothing.
+		assert_eq!(Registrar::on_runtime_upgrade(), 0);
+		assert_eq!(Registrar::role_of(&org, &issuer), None);
+	});
+}